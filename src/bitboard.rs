@@ -0,0 +1,450 @@
+use alloc::vec::Vec;
+
+use crate::{
+    BanishBoard, Board, GroupSet, Groups, ParseError,
+    dense::{DenseHandle, DenseIndex},
+    gravity::GravityBoard,
+    parse_rows,
+    quicksim::{QuickSimBoard, SimResult},
+    standard::{ColorBoard, GroupFromColorBoard, NuisanceBoard},
+};
+
+pub const WIDTH: usize = 6;
+pub const HEIGHT: usize = 12;
+
+///Every column takes up 16 bits of a plane, the 4 bits above the top row are always empty
+/// so that shifting a column up or down never spills into the next one.
+const COLUMN_BITS: usize = 16;
+const COLUMN: u128 = (1 << HEIGHT) - 1;
+const FIELD: u128 = rows_from(0);
+
+///Every cell at row `y` or above, in every column.
+const fn rows_from(y: usize) -> u128 {
+    let mut rows = 0;
+    let mut x = 0;
+    while x < WIDTH {
+        rows |= (COLUMN & !((1 << y) - 1)) << (x * COLUMN_BITS);
+        x += 1;
+    }
+    rows
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Tile {
+    #[default]
+    Air,
+    Nuisance,
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    Purple,
+}
+
+impl Tile {
+    const PLANES: [Tile; 6] = [
+        Tile::Nuisance,
+        Tile::Red,
+        Tile::Green,
+        Tile::Blue,
+        Tile::Yellow,
+        Tile::Purple,
+    ];
+    fn plane(self) -> Option<usize> {
+        Tile::PLANES.iter().position(|&t| t == self)
+    }
+}
+
+///A cell of a `BitBoard`, stored as its bit position in the planes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Cell(u8);
+
+impl Cell {
+    pub fn new(x: usize, y: usize) -> Cell {
+        debug_assert!(x < WIDTH && y < HEIGHT);
+        Cell((x * COLUMN_BITS + y) as u8)
+    }
+    pub fn x(self) -> usize {
+        self.0 as usize / COLUMN_BITS
+    }
+    pub fn y(self) -> usize {
+        self.0 as usize % COLUMN_BITS
+    }
+    fn bit(self) -> u128 {
+        1 << self.0
+    }
+}
+
+impl DenseHandle for Cell {
+    const CAPACITY: usize = WIDTH * COLUMN_BITS;
+    fn to_index(&self) -> usize {
+        self.0 as usize
+    }
+    fn from_index(index: usize) -> Self {
+        Cell(index as u8)
+    }
+}
+
+///A group of `Cell`s packed into a single word, the fastest `GroupSet` for a `BitBoard`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct CellSet(pub u128);
+
+pub struct CellSetIter(u128);
+
+impl Iterator for CellSetIter {
+    type Item = Cell;
+    fn next(&mut self) -> Option<Cell> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(Cell(bit as u8))
+    }
+}
+
+impl IntoIterator for CellSet {
+    type Item = Cell;
+    type IntoIter = CellSetIter;
+    fn into_iter(self) -> CellSetIter {
+        CellSetIter(self.0)
+    }
+}
+
+impl FromIterator<Cell> for CellSet {
+    fn from_iter<T: IntoIterator<Item = Cell>>(iter: T) -> Self {
+        CellSet(iter.into_iter().fold(0, |set, cell| set | cell.bit()))
+    }
+}
+
+impl Extend<Cell> for CellSet {
+    fn extend<T: IntoIterator<Item = Cell>>(&mut self, iter: T) {
+        for cell in iter {
+            self.0 |= cell.bit();
+        }
+    }
+}
+
+impl GroupSet<Cell> for CellSet {
+    type Index = DenseIndex<Cell>;
+    fn contains(&self, handle: &Cell) -> bool {
+        self.0 & handle.bit() != 0
+    }
+    fn insert(&mut self, handle: Cell) -> bool {
+        let was_absent = self.0 & handle.bit() == 0;
+        self.0 |= handle.bit();
+        was_absent
+    }
+    fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+    fn handles(&self) -> impl Iterator<Item = Cell> {
+        CellSetIter(self.0)
+    }
+}
+
+///Every cell next to a cell in `mask`, not including `mask` itself.
+fn dilate(mask: u128) -> u128 {
+    ((mask << 1) | (mask >> 1) | (mask << COLUMN_BITS) | (mask >> COLUMN_BITS)) & FIELD & !mask
+}
+
+///Grows `seed` through `plane` until it covers the whole connected group.
+fn flood(seed: u128, plane: u128) -> u128 {
+    let mut group = seed;
+    loop {
+        let next = (group | dilate(group)) & plane;
+        if next == group {
+            return group;
+        }
+        group = next;
+    }
+}
+
+const SMEAR: [(usize, u128); 4] = [
+    (1, rows_from(1)),
+    (2, rows_from(2)),
+    (4, rows_from(4)),
+    (8, rows_from(8)),
+];
+
+///Every cell with `air` somewhere below it in the same column, smeared up every column at once.
+fn above(air: u128) -> u128 {
+    let mut above = (air << 1) & FIELD;
+    //shifting by 8 can spill into the bottom rows of the next column, so those are masked off
+    for (k, rows) in SMEAR {
+        above |= (above << k) & rows;
+    }
+    above
+}
+
+///Every connected group in `plane` with at least `pieces_to_pop` cells, as masks.
+fn plane_groups(plane: u128, pieces_to_pop: usize) -> impl Iterator<Item = u128> {
+    let mut left = plane;
+    core::iter::from_fn(move || {
+        while left != 0 {
+            let group = flood(left & left.wrapping_neg(), left);
+            left &= !group;
+            if group.count_ones() as usize >= pieces_to_pop {
+                return Some(group);
+            }
+        }
+        None
+    })
+}
+
+///A standard 6x12 board stored as one bitplane per colour (and one for nuisance).
+///
+/// Grouping, popping and falling are all done a whole plane at a time,
+/// so this is the board to use when searching through millions of chains.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct BitBoard {
+    planes: [u128; 6],
+}
+
+///The result of `BitBoard::fast_chain`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct FastChain {
+    pub chain: u64,
+    pub pieces_cleared: u64,
+}
+
+impl BitBoard {
+    pub fn get(&self, cell: Cell) -> Tile {
+        Tile::PLANES
+            .iter()
+            .zip(&self.planes)
+            .find(|(_, plane)| *plane & cell.bit() != 0)
+            .map(|(&tile, _)| tile)
+            .unwrap_or(Tile::Air)
+    }
+    pub fn set(&mut self, cell: Cell, to: Tile) {
+        for plane in &mut self.planes {
+            *plane &= !cell.bit();
+        }
+        if let Some(plane) = to.plane() {
+            self.planes[plane] |= cell.bit();
+        }
+    }
+    ///The bitplane for a kind of tile, `Tile::Air` gives every empty cell.
+    pub fn plane(&self, tile: Tile) -> u128 {
+        match tile.plane() {
+            Some(plane) => self.planes[plane],
+            None => FIELD & !self.occupied(),
+        }
+    }
+    ///Every cell that isn't air.
+    pub fn occupied(&self) -> u128 {
+        self.planes.iter().fold(0, |acc, plane| acc | plane)
+    }
+    ///Each colour group of at least `pieces_to_pop` tiles, as masks.
+    pub fn pop_groups(&self, pieces_to_pop: usize) -> impl Iterator<Item = u128> {
+        self.planes[1..]
+            .iter()
+            .flat_map(move |&plane| plane_groups(plane, pieces_to_pop))
+    }
+    ///Every cell that would be banished by one cast of Owanimo, including nuisance caught up in it.
+    pub fn pop_mask(&self, pieces_to_pop: usize) -> u128 {
+        let popped = self
+            .pop_groups(pieces_to_pop)
+            .fold(0, |acc, group| acc | group);
+        popped | (dilate(popped) & self.planes[0])
+    }
+    ///Banishes every cell in `mask` at once.
+    pub fn clear(&mut self, mask: u128) {
+        for plane in &mut self.planes {
+            *plane &= !mask;
+        }
+    }
+    ///Runs a whole chain with nothing but plane operations, for when only the chain length matters.
+    pub fn fast_chain(&mut self, pieces_to_pop: usize) -> FastChain {
+        let mut result = FastChain::default();
+        loop {
+            self.fall();
+            let mask = self.pop_mask(pieces_to_pop);
+            if mask == 0 {
+                return result;
+            }
+            self.clear(mask);
+            result.chain += 1;
+            result.pieces_cleared += mask.count_ones() as u64;
+        }
+    }
+    ///Runs and scores a whole chain with nothing but plane operations, for searches that need the score.
+    ///
+    /// Gives the same `SimResult` as `quick_sim` with `ColoredPiecesCleared`, no point bonus,
+    /// and a `ColorBonusTable` and `GroupBonusTable` made from `color_bonus` and `group_bonus`.
+    pub fn fast_sim(
+        &mut self,
+        pieces_to_pop: usize,
+        chain_power_table: &[u64],
+        color_bonus: &[u64],
+        group_bonus: &[u64],
+    ) -> SimResult {
+        let lookup = |table: &[u64], i: usize| *table.get(i).or(table.last()).unwrap_or(&0);
+        let mut result = SimResult {
+            score: 0,
+            chain: 0,
+            pieces_cleared: 0,
+            max_pieces_at_once: 0,
+            link_scores: Vec::new(),
+            all_clear: false,
+            bonus_garbage: 0,
+        };
+        loop {
+            self.fall();
+            let mut popped = 0;
+            let mut colors = 0;
+            let mut groups = 0;
+            for &plane in &self.planes[1..] {
+                let before = popped;
+                for group in plane_groups(plane, pieces_to_pop) {
+                    popped |= group;
+                    groups += lookup(group_bonus, group.count_ones() as usize);
+                }
+                colors += (popped != before) as usize;
+            }
+            if popped == 0 {
+                break;
+            }
+            let cleared = popped.count_ones() as u64;
+            let multiplier = lookup(chain_power_table, result.chain as usize)
+                + lookup(color_bonus, colors)
+                + groups;
+            let score = 10 * cleared * multiplier;
+            self.clear(popped | (dilate(popped) & self.planes[0]));
+            result.score += score;
+            result.link_scores.push(score);
+            result.pieces_cleared += cleared;
+            result.max_pieces_at_once = result.max_pieces_at_once.max(cleared);
+            result.chain += 1;
+        }
+        result.all_clear = result.chain > 0 && self.occupied() == 0;
+        result
+    }
+}
+
+///Parses rows from top to bottom, `_` or space is air, `o` is nuisance, and `rgbyp` are colours.
+impl core::str::FromStr for BitBoard {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut this = Self::default();
+        parse_rows(s, WIDTH, HEIGHT, |(x, y), chr| {
+            let tile = match chr {
+                ' ' | '_' => Tile::Air,
+                'O' | 'o' | '0' => Tile::Nuisance,
+                'R' | 'r' => Tile::Red,
+                'G' | 'g' => Tile::Green,
+                'B' | 'b' => Tile::Blue,
+                'Y' | 'y' => Tile::Yellow,
+                'P' | 'p' => Tile::Purple,
+                c => {
+                    return Err(ParseError::UnknownSymbol {
+                        at: (x, y),
+                        symbol: c,
+                    });
+                }
+            };
+            this.set(Cell::new(x, y), tile);
+            Ok(())
+        })?;
+        Ok(this)
+    }
+}
+
+impl Board for BitBoard {
+    type Handle = Cell;
+
+    fn tiles(&self) -> impl Iterator<Item = Cell> {
+        (0..WIDTH).flat_map(|x| (0..HEIGHT).map(move |y| Cell::new(x, y)))
+    }
+
+    fn neighbors(&self, handle: &Cell) -> impl Iterator<Item = Cell> {
+        CellSetIter(dilate(handle.bit()))
+    }
+
+    fn connects(&self, a: &Cell, b: &Cell) -> bool {
+        let both = a.bit() | b.bit();
+        self.planes[1..].iter().any(|plane| plane & both == both)
+    }
+
+    fn air(&self, handle: &Cell) -> bool {
+        self.occupied() & handle.bit() == 0
+    }
+
+    fn all_clear(&self) -> bool {
+        self.occupied() == 0
+    }
+
+    fn owanimo_grouper_in<S: GroupSet<Cell>>(&self) -> Groups<Cell, S> {
+        let mut groups = Groups::default();
+        for &plane in &self.planes[1..] {
+            let mut left = plane;
+            while left != 0 {
+                let group = flood(left & left.wrapping_neg(), left);
+                left &= !group;
+                groups.push(CellSetIter(group).collect());
+            }
+        }
+        //air and nuisance never connect, so they all get groups of their own
+        for cell in CellSetIter(FIELD & !(self.occupied() & !self.planes[0])) {
+            groups.push([cell].into_iter().collect());
+        }
+        groups
+    }
+}
+
+impl ColorBoard for BitBoard {
+    type Color = Tile;
+    fn color(&self, handle: &Cell) -> Option<Tile> {
+        match self.get(*handle) {
+            Tile::Air | Tile::Nuisance => None,
+            x => Some(x),
+        }
+    }
+}
+
+impl GroupFromColorBoard for BitBoard {}
+
+impl NuisanceBoard for BitBoard {
+    fn nuisance(&self, handle: &Cell) -> bool {
+        self.planes[0] & handle.bit() != 0
+    }
+}
+
+impl BanishBoard for BitBoard {
+    fn banish(&mut self, handle: Cell) {
+        self.clear(handle.bit());
+    }
+}
+
+impl GravityBoard for BitBoard {
+    fn fall(&mut self) -> bool {
+        let mut did_fall = false;
+        //every being with air under it drops a row, in every column at once, until nothing is left hanging
+        loop {
+            let occupied = self.occupied();
+            let falling = occupied & above(FIELD & !occupied);
+            if falling == 0 {
+                return did_fall;
+            }
+            did_fall = true;
+            for plane in &mut self.planes {
+                *plane = (*plane & !falling) | ((*plane & falling) >> 1);
+            }
+        }
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<Cell>) -> bool {
+        let before = self.planes;
+        let did_fall = self.fall();
+        let moved = before
+            .iter()
+            .zip(&self.planes)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+        changed.extend(CellSetIter(moved));
+        did_fall
+    }
+}
+
+///Goes through the generic groups so that any scorer can be used, see `BitBoard::fast_sim` for the fast path.
+impl QuickSimBoard<CellSet> for BitBoard {}
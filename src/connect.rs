@@ -0,0 +1,157 @@
+use crate::{
+    BanishBoard, Board, PlaceBoard,
+    gravity::GravityBoard,
+    grid::{CartesianBoard, GridTileBoard, tile_hooks},
+    quicksim::QuickSimBoard,
+};
+
+///The 8 moves of a knight, for boards where beings link up in L shapes.
+pub const KNIGHT: [(isize, isize); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+///Which tiles around a tile count as its neighbors on a grid board.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Neighborhood<'a> {
+    ///The 4 tiles sharing an edge.
+    #[default]
+    Four,
+    ///The 8 tiles sharing an edge or a corner.
+    Eight,
+    ///Any offsets you like, such as `KNIGHT`.
+    ///
+    /// Remember to list every offset both ways round, or groups may only link up in one direction.
+    Offsets(&'a [(isize, isize)]),
+}
+
+impl<'a> Neighborhood<'a> {
+    ///The `(dx, dy)` offsets of every neighbor.
+    pub fn offsets(self) -> &'a [(isize, isize)] {
+        match self {
+            Neighborhood::Four => &[(-1, 0), (0, -1), (1, 0), (0, 1)],
+            Neighborhood::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Neighborhood::Offsets(offsets) => offsets,
+        }
+    }
+}
+
+///Swaps out the neighbors of a grid board, for games like Columns where beings also link up diagonally.
+///
+/// `grouping` is used to find groups, and `nuisance` decides which popped beings drag nuisance along with them,
+/// so diagonal groups can still only clear nuisance orthogonally.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Connected<'a, B> {
+    pub inner: B,
+    pub grouping: Neighborhood<'a>,
+    pub nuisance: Neighborhood<'a>,
+}
+
+impl<'a, B: CartesianBoard> Connected<'a, B> {
+    ///Uses `neighborhood` for both grouping and nuisance.
+    pub fn new(inner: B, neighborhood: Neighborhood<'a>) -> Self {
+        Connected {
+            inner,
+            grouping: neighborhood,
+            nuisance: neighborhood,
+        }
+    }
+    ///The tiles at every offset of `neighborhood` from `(x, y)` that are on the board.
+    fn around(
+        &self,
+        neighborhood: Neighborhood<'a>,
+        (x, y): (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (w, h) = (self.inner.width(), self.inner.height());
+        neighborhood.offsets().iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx).filter(|&nx| nx < w)?;
+            let ny = y.checked_add_signed(dy).filter(|&ny| ny < h)?;
+            Some((nx, ny))
+        })
+    }
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard> Board for Connected<'a, B> {
+    type Handle = (usize, usize);
+
+    fn tiles(&self) -> impl Iterator<Item = Self::Handle> {
+        self.inner.tiles()
+    }
+
+    fn neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.around(self.grouping, *handle)
+    }
+
+    fn nuisance_neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.around(self.nuisance, *handle)
+    }
+
+    tile_hooks!();
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard> CartesianBoard for Connected<'a, B> {
+    fn width(&self) -> usize {
+        self.inner.width()
+    }
+    fn height(&self) -> usize {
+        self.inner.height()
+    }
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard> GridTileBoard for Connected<'a, B> {
+    type Tile = B::Tile;
+    fn tile(&self, handle: &Self::Handle) -> B::Tile {
+        self.inner.tile(handle)
+    }
+    fn set_tile(&mut self, handle: &Self::Handle, tile: B::Tile) {
+        self.inner.set_tile(handle, tile)
+    }
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard + BanishBoard> BanishBoard for Connected<'a, B> {
+    fn banish(&mut self, handle: Self::Handle) {
+        self.inner.banish(handle)
+    }
+    fn blast(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.inner.blast(handle)
+    }
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard + PlaceBoard> PlaceBoard for Connected<'a, B> {
+    type Being = B::Being;
+    fn place(&mut self, handle: Self::Handle, being: B::Being) {
+        self.inner.place(handle, being)
+    }
+    fn step(&self, handle: &Self::Handle, by: (isize, isize)) -> Option<Self::Handle> {
+        self.inner.step(handle, by)
+    }
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard + GravityBoard> GravityBoard for Connected<'a, B> {
+    fn fall(&mut self) -> bool {
+        self.inner.fall()
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool {
+        self.inner.fall_tracked(changed)
+    }
+}
+
+impl<'a, B: CartesianBoard + GridTileBoard + BanishBoard + GravityBoard> QuickSimBoard
+    for Connected<'a, B>
+{
+}
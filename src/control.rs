@@ -0,0 +1,156 @@
+use crate::{PlaceBoard, gravity::GravityBoard, pieces::Piece};
+
+///Which side of the pivot the satellite is on.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Orientation {
+    #[default]
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Orientation {
+    ///Where the satellite is, relative to the pivot.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Orientation::Up => (0, 1),
+            Orientation::Right => (1, 0),
+            Orientation::Down => (0, -1),
+            Orientation::Left => (-1, 0),
+        }
+    }
+    pub fn cw(self) -> Self {
+        match self {
+            Orientation::Up => Orientation::Right,
+            Orientation::Right => Orientation::Down,
+            Orientation::Down => Orientation::Left,
+            Orientation::Left => Orientation::Up,
+        }
+    }
+    pub fn ccw(self) -> Self {
+        self.cw().cw().cw()
+    }
+}
+
+///The pair the player is in control of, a pivot with a satellite that turns around it.
+///
+/// Every move goes through `PlaceBoard::step` and checks `Board::air`, and does nothing if it's blocked.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ActivePair<C, H = (usize, usize)> {
+    pub pivot: C,
+    pub satellite: C,
+    ///Where the pivot is.
+    pub position: H,
+    pub orientation: Orientation,
+    ///Was the last turn blocked on both sides? If so, the next turn in either direction is a quick-turn.
+    pub quick_turn: bool,
+}
+
+impl<C: Copy, H: Copy> ActivePair<C, H> {
+    ///A pair at `position`, with the satellite on top.
+    pub fn new(pivot: C, satellite: C, position: H) -> Self {
+        ActivePair {
+            pivot,
+            satellite,
+            position,
+            orientation: Orientation::Up,
+            quick_turn: false,
+        }
+    }
+    ///Takes control of `piece` if it's a pair.
+    pub fn from_piece(piece: Piece<C>, position: H) -> Option<Self> {
+        match piece {
+            Piece::Pair { pivot, satellite } => Some(ActivePair::new(pivot, satellite, position)),
+            _ => None,
+        }
+    }
+    pub fn satellite_position<B: PlaceBoard<Handle = H>>(&self, board: &B) -> Option<H> {
+        board.step(&self.position, self.orientation.offset())
+    }
+    ///Would the pair fit with its pivot at `position`, turned to `orientation`?
+    fn fits<B: PlaceBoard<Handle = H>>(board: &B, position: H, orientation: Orientation) -> bool {
+        board.air(&position)
+            && board
+                .step(&position, orientation.offset())
+                .is_some_and(|satellite| board.air(&satellite))
+    }
+    ///Moves the pair by `by`, returning whether it could.
+    fn try_move<B: PlaceBoard<Handle = H>>(&mut self, board: &B, by: (isize, isize)) -> bool {
+        match board.step(&self.position, by) {
+            Some(to) if Self::fits(board, to, self.orientation) => {
+                self.position = to;
+                self.quick_turn = false;
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn shift_left<B: PlaceBoard<Handle = H>>(&mut self, board: &B) -> bool {
+        self.try_move(board, (-1, 0))
+    }
+    pub fn shift_right<B: PlaceBoard<Handle = H>>(&mut self, board: &B) -> bool {
+        self.try_move(board, (1, 0))
+    }
+    ///Moves the pair down a row, returning false once it has landed.
+    pub fn soft_drop<B: PlaceBoard<Handle = H>>(&mut self, board: &B) -> bool {
+        self.try_move(board, (0, -1))
+    }
+    ///Is there something right under the pair?
+    pub fn landed<B: PlaceBoard<Handle = H>>(&self, board: &B) -> bool {
+        !board
+            .step(&self.position, (0, -1))
+            .is_some_and(|to| Self::fits(board, to, self.orientation))
+    }
+    ///Turns the satellite to `orientation`, kicking the pivot away from a wall or up off the floor if it has to.
+    fn try_turn<B: PlaceBoard<Handle = H>>(&mut self, board: &B, orientation: Orientation) -> bool {
+        let (dx, dy) = orientation.offset();
+        let kicked = board.step(&self.position, (-dx, -dy));
+        for position in [Some(self.position), kicked].into_iter().flatten() {
+            if Self::fits(board, position, orientation) {
+                self.position = position;
+                self.orientation = orientation;
+                self.quick_turn = false;
+                return true;
+            }
+        }
+        false
+    }
+    ///Turns the satellite `to`, or flips it over if this is the second turn in a row blocked on both sides.
+    fn turn<B: PlaceBoard<Handle = H>>(&mut self, board: &B, to: Orientation) -> bool {
+        if self.try_turn(board, to) {
+            return true;
+        }
+        //only stuck in a well one wide if there's something on both sides of the pivot
+        let blocked = |by| {
+            !board
+                .step(&self.position, by)
+                .is_some_and(|side| board.air(&side))
+        };
+        if !(blocked((-1, 0)) && blocked((1, 0))) {
+            self.quick_turn = false;
+            return false;
+        }
+        if self.quick_turn {
+            return self.try_turn(board, self.orientation.cw().cw());
+        }
+        self.quick_turn = true;
+        false
+    }
+    ///Turns the satellite clockwise around the pivot.
+    pub fn rotate_cw<B: PlaceBoard<Handle = H>>(&mut self, board: &B) -> bool {
+        self.turn(board, self.orientation.cw())
+    }
+    ///Turns the satellite counterclockwise around the pivot.
+    pub fn rotate_ccw<B: PlaceBoard<Handle = H>>(&mut self, board: &B) -> bool {
+        self.turn(board, self.orientation.ccw())
+    }
+    ///Writes the pair into `board` and lets it fall, so a pair landing on uneven ground splits apart.
+    pub fn lock<B: PlaceBoard<Handle = H, Being = C> + GravityBoard>(self, board: &mut B) {
+        if let Some(satellite) = self.satellite_position(board) {
+            board.place(satellite, self.satellite);
+        }
+        board.place(self.position, self.pivot);
+        board.fall();
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{BoardHandle, GroupId, GroupIndex, GroupSet};
+
+///A handle that can be numbered `0..CAPACITY`, like the cells of a fixed size grid.
+///
+/// Handles like this can have their groups stored in a `BitSet` instead of a `HashSet`,
+/// which skips hashing entirely.
+pub trait DenseHandle: BoardHandle {
+    ///How many different handles there are, every `to_index` must be less than this.
+    const CAPACITY: usize;
+    fn to_index(&self) -> usize;
+    fn from_index(index: usize) -> Self;
+}
+
+///A fixed-width set of `DenseHandle`s, one bit per handle.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitSet<H: DenseHandle> {
+    words: Vec<u64>,
+    phantom: core::marker::PhantomData<H>,
+}
+
+impl<H: DenseHandle> Default for BitSet<H> {
+    fn default() -> Self {
+        BitSet {
+            words: vec![0; H::CAPACITY.div_ceil(64)],
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<H: DenseHandle> BitSet<H> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///The raw words of the set, bit `i % 64` of word `i / 64` is the handle with index `i`.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+    pub fn contains(&self, handle: &H) -> bool {
+        let i = handle.to_index();
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+    ///Adds the handle, returning `true` if it wasn't already in the set.
+    pub fn insert(&mut self, handle: H) -> bool {
+        let i = handle.to_index();
+        let word = &mut self.words[i / 64];
+        let was_absent = *word & (1 << (i % 64)) == 0;
+        *word |= 1 << (i % 64);
+        was_absent
+    }
+    ///Removes the handle, returning `true` if it was in the set.
+    pub fn remove(&mut self, handle: &H) -> bool {
+        let i = handle.to_index();
+        let word = &mut self.words[i / 64];
+        let was_present = *word & (1 << (i % 64)) != 0;
+        *word &= !(1 << (i % 64));
+        was_present
+    }
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+    ///Adds every handle in `other` to this set.
+    pub fn union_with(&mut self, other: &Self) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+    ///Do the two sets share any handles?
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).any(|(a, b)| a & b != 0)
+    }
+    pub fn iter(&self) -> BitSetIter<'_, H> {
+        BitSetIter {
+            words: &self.words,
+            word: 0,
+            bits: self.words.first().copied().unwrap_or(0),
+            phantom: Default::default(),
+        }
+    }
+}
+
+pub struct BitSetIter<'a, H: DenseHandle> {
+    words: &'a [u64],
+    word: usize,
+    bits: u64,
+    phantom: core::marker::PhantomData<H>,
+}
+
+impl<'a, H: DenseHandle> Iterator for BitSetIter<'a, H> {
+    type Item = H;
+    fn next(&mut self) -> Option<H> {
+        while self.bits == 0 {
+            self.word += 1;
+            self.bits = *self.words.get(self.word)?;
+        }
+        let bit = self.bits.trailing_zeros() as usize;
+        self.bits &= self.bits - 1;
+        Some(H::from_index(self.word * 64 + bit))
+    }
+}
+
+impl<H: DenseHandle> FromIterator<H> for BitSet<H> {
+    fn from_iter<T: IntoIterator<Item = H>>(iter: T) -> Self {
+        let mut set = BitSet::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<H: DenseHandle> Extend<H> for BitSet<H> {
+    fn extend<T: IntoIterator<Item = H>>(&mut self, iter: T) {
+        for h in iter {
+            self.insert(h);
+        }
+    }
+}
+
+impl<H: DenseHandle> IntoIterator for BitSet<H> {
+    type Item = H;
+    type IntoIter = alloc::vec::IntoIter<H>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<'a, H: DenseHandle> IntoIterator for &'a BitSet<H> {
+    type Item = H;
+    type IntoIter = BitSetIter<'a, H>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<H: DenseHandle> GroupSet<H> for BitSet<H> {
+    type Index = DenseIndex<H>;
+    fn contains(&self, handle: &H) -> bool {
+        BitSet::contains(self, handle)
+    }
+    fn insert(&mut self, handle: H) -> bool {
+        BitSet::insert(self, handle)
+    }
+    fn len(&self) -> usize {
+        BitSet::len(self)
+    }
+    fn is_empty(&self) -> bool {
+        BitSet::is_empty(self)
+    }
+    fn handles(&self) -> impl Iterator<Item = H> {
+        self.iter()
+    }
+}
+
+///A `GroupIndex` for `DenseHandle`s, stored as a flat table instead of a `HashMap`.
+#[derive(Clone, Debug)]
+pub struct DenseIndex<H: DenseHandle> {
+    table: Vec<Option<GroupId>>,
+    phantom: core::marker::PhantomData<H>,
+}
+
+impl<H: DenseHandle> Default for DenseIndex<H> {
+    fn default() -> Self {
+        DenseIndex {
+            table: vec![None; H::CAPACITY],
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<H: DenseHandle> GroupIndex<H> for DenseIndex<H> {
+    fn get(&self, handle: &H) -> Option<GroupId> {
+        self.table[handle.to_index()]
+    }
+    fn insert(&mut self, handle: H, id: GroupId) {
+        self.table[handle.to_index()] = Some(id);
+    }
+    fn remove(&mut self, handle: &H) {
+        self.table[handle.to_index()] = None;
+    }
+}
@@ -0,0 +1,244 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{ParseError, gravity::GravityBoard, quicksim::SimResult, rng::Rng};
+
+///The numbers that make up Fever mode, the defaults are from Puyo Puyo Fever, counting time in frames at 60 per second.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FeverRules {
+    ///How many offsets fill the gauge.
+    pub gauge_size: u32,
+    ///The length of the first fever chain.
+    pub start_chain: u32,
+    pub min_chain: u32,
+    pub max_chain: u32,
+    ///How long Fever mode lasts to begin with.
+    pub start_time: u32,
+    ///How much time every link of a fever chain adds.
+    pub time_per_link: u32,
+    ///The timer never goes above this.
+    pub max_time: u32,
+}
+
+impl Default for FeverRules {
+    fn default() -> Self {
+        FeverRules {
+            gauge_size: 7,
+            start_chain: 5,
+            min_chain: 3,
+            max_chain: 15,
+            start_time: 15 * 60,
+            time_per_link: 30,
+            max_time: 30 * 60,
+        }
+    }
+}
+
+///The pre-built chains that are dropped in during Fever mode.
+///
+/// Written as text, every board comes after a header with its chain length, like `[5]`,
+/// and is drawn the same way as the board's own `FromStr`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FeverBook<B> {
+    ///Every board, along with the length of its chain.
+    pub boards: Vec<(u32, B)>,
+}
+
+impl<B> Default for FeverBook<B> {
+    fn default() -> Self {
+        FeverBook { boards: Vec::new() }
+    }
+}
+
+impl<B> FeverBook<B> {
+    ///Picks a board with a chain of `length` using `rng`.
+    ///
+    /// If there isn't one, the longest shorter chain is used, and failing that the shortest longer chain.
+    pub fn pick(&self, length: u32, rng: &mut impl Rng) -> Option<&B> {
+        let best = self
+            .boards
+            .iter()
+            .map(|(l, _)| *l)
+            .filter(|&l| l <= length)
+            .max()
+            .or_else(|| self.boards.iter().map(|(l, _)| *l).min())?;
+        let count = self.boards.iter().filter(|(l, _)| *l == best).count();
+        let nth = rng.below(count as u64) as usize;
+        self.boards
+            .iter()
+            .filter(|(l, _)| *l == best)
+            .nth(nth)
+            .map(|(_, board)| board)
+    }
+}
+
+///Why a `FeverBook` couldn't be read from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookError {
+    ///A header that isn't a number in brackets, or a board without a header.
+    BadHeader { line: usize },
+    ///One of the boards couldn't be read.
+    Board(ParseError),
+}
+
+impl From<ParseError> for BookError {
+    fn from(error: ParseError) -> Self {
+        BookError::Board(error)
+    }
+}
+
+impl core::fmt::Display for BookError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BookError::BadHeader { line } => write!(f, "Bad Header on line {}", line),
+            BookError::Board(error) => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for BookError {}
+
+impl<B: core::str::FromStr<Err = ParseError>> core::str::FromStr for FeverBook<B> {
+    type Err = BookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut book = FeverBook::default();
+        let mut current: Option<(u32, String)> = None;
+        for (i, line) in s.lines().enumerate() {
+            let trimmed = line.trim();
+            if let Some(header) = trimmed.strip_prefix('[') {
+                let length = header
+                    .strip_suffix(']')
+                    .and_then(|length| length.trim().parse().ok())
+                    .ok_or(BookError::BadHeader { line: i + 1 })?;
+                if let Some((length, rows)) = current.replace((length, String::new())) {
+                    book.boards.push((length, rows.parse()?));
+                }
+            } else if let Some((_, rows)) = &mut current {
+                rows.push_str(line);
+                rows.push('\n');
+            } else if !trimmed.is_empty() {
+                return Err(BookError::BadHeader { line: i + 1 });
+            }
+        }
+        if let Some((length, rows)) = current {
+            book.boards.push((length, rows.parse()?));
+        }
+        Ok(book)
+    }
+}
+
+///Keeps track of Fever mode for one player: the gauge, the chain length, the timer, and the board to go back to.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Fever<B> {
+    pub rules: FeverRules,
+    ///How many offsets have gone into the gauge.
+    pub gauge: u32,
+    ///The length of the next fever chain.
+    pub chain_length: u32,
+    pub time_left: u32,
+    ///The regular board, put aside while Fever mode is going.
+    pub saved: Option<B>,
+}
+
+impl<B> Default for Fever<B> {
+    fn default() -> Self {
+        Fever::new(FeverRules::default())
+    }
+}
+
+impl<B> Fever<B> {
+    pub fn new(rules: FeverRules) -> Self {
+        Fever {
+            rules,
+            gauge: 0,
+            chain_length: rules.start_chain,
+            time_left: 0,
+            saved: None,
+        }
+    }
+    ///Is Fever mode going right now?
+    pub fn is_active(&self) -> bool {
+        self.saved.is_some()
+    }
+    pub fn is_gauge_full(&self) -> bool {
+        self.gauge >= self.rules.gauge_size
+    }
+    ///A chain cancelled some nuisance, so the gauge goes up, returning whether it's full.
+    ///
+    /// Offsets during Fever mode don't count.
+    pub fn add_offset(&mut self) -> bool {
+        if !self.is_active() {
+            self.gauge = (self.gauge + 1).min(self.rules.gauge_size);
+        }
+        self.is_gauge_full()
+    }
+}
+
+impl<B: GravityBoard + Clone> Fever<B> {
+    ///Puts a fever chain from `book` onto `board`, letting it fall into place.
+    fn load(&self, board: &mut B, book: &FeverBook<B>, rng: &mut impl Rng) {
+        if let Some(preset) = book.pick(self.chain_length, rng) {
+            *board = preset.clone();
+            board.fall();
+        }
+    }
+    ///Starts Fever mode if the gauge is full, putting `board` aside and dropping in a fever chain.
+    pub fn start(&mut self, board: &mut B, book: &FeverBook<B>, rng: &mut impl Rng) -> bool {
+        if self.is_active() || !self.is_gauge_full() {
+            return false;
+        }
+        self.gauge = 0;
+        self.time_left = self.rules.start_time;
+        self.saved = Some(board.clone());
+        self.load(board, book, rng);
+        true
+    }
+    ///Call with the result of every `quick_sim` during Fever mode, returning whether the fever chain went off in full.
+    ///
+    /// A full chain makes the next one longer, a chain that falls short makes it shorter,
+    /// either way every link adds time and a new fever chain is dropped in.
+    pub fn after_chain(
+        &mut self,
+        board: &mut B,
+        result: &SimResult,
+        book: &FeverBook<B>,
+        rng: &mut impl Rng,
+    ) -> bool {
+        if !self.is_active() || result.chain == 0 {
+            return false;
+        }
+        let success = result.chain >= self.chain_length as u64;
+        self.chain_length = if success {
+            self.chain_length + 1
+        } else {
+            self.chain_length.saturating_sub(1)
+        }
+        //not `clamp`, which panics if the rules have `min_chain` above `max_chain`
+        .max(self.rules.min_chain)
+        .min(self.rules.max_chain);
+        let bonus = self.rules.time_per_link.saturating_mul(result.chain as u32);
+        self.time_left = self
+            .time_left
+            .saturating_add(bonus)
+            .min(self.rules.max_time);
+        self.load(board, book, rng);
+        success
+    }
+    ///Runs the timer down by `elapsed`, putting the regular board back when it runs out.
+    ///
+    /// Returns whether Fever mode ended.
+    pub fn tick(&mut self, board: &mut B, elapsed: u32) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+        self.time_left = self.time_left.saturating_sub(elapsed);
+        if self.time_left > 0 {
+            return false;
+        }
+        if let Some(saved) = self.saved.take() {
+            *board = saved;
+        }
+        true
+    }
+}
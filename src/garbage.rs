@@ -0,0 +1,161 @@
+///How many points make one piece of nuisance in Puyo Puyo Tsu.
+pub const TSU_TARGET: u64 = 70;
+///How many points make one piece of nuisance in Puyo Puyo Fever.
+pub const FEVER_TARGET: u64 = 120;
+
+///What to do with the points that don't add up to a whole piece of nuisance.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Rounding {
+    ///Send whole pieces only, and keep the rest for later, like the official games.
+    #[default]
+    Down,
+    ///Send one more piece when at least half of the target is left over, and owe the difference.
+    Nearest,
+    ///Send one more piece whenever anything is left over, and owe the difference.
+    Up,
+}
+
+///Turns scores into nuisance to send to the opponent.
+///
+/// Points that don't make up a whole piece of nuisance are carried over to the next link,
+/// and to the next turn too, for as long as the same `GarbageCalculator` is used.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GarbageCalculator {
+    ///How many points make one piece of nuisance, such as `TSU_TARGET`.
+    pub target_points: u64,
+    pub rounding: Rounding,
+    ///The points carried over, negative when rounding sent more than was earned.
+    pub leftover: i64,
+}
+
+impl Default for GarbageCalculator {
+    fn default() -> Self {
+        GarbageCalculator::new(TSU_TARGET)
+    }
+}
+
+impl GarbageCalculator {
+    pub fn new(target_points: u64) -> Self {
+        GarbageCalculator {
+            target_points,
+            rounding: Rounding::default(),
+            leftover: 0,
+        }
+    }
+    ///Turns the score of one link into nuisance.
+    pub fn add_link(&mut self, score: u64) -> u64 {
+        let target = self.target_points.max(1) as i64;
+        let total = score as i64 + self.leftover;
+        let nuisance = match self.rounding {
+            Rounding::Down => total.div_euclid(target),
+            Rounding::Nearest => (total + target / 2).div_euclid(target),
+            Rounding::Up => (total + target - 1).div_euclid(target),
+        }
+        .max(0);
+        self.leftover = total - nuisance * target;
+        nuisance as u64
+    }
+    ///Turns the score of every link into nuisance, one link at a time.
+    pub fn add_links(&mut self, scores: impl IntoIterator<Item = u64>) -> u64 {
+        scores.into_iter().map(|score| self.add_link(score)).sum()
+    }
+    ///Turns the whole chain from `quick_sim` into nuisance, including any `bonus_garbage`.
+    #[cfg(feature = "quicksim")]
+    pub fn add_sim(&mut self, result: &crate::quicksim::SimResult) -> u64 {
+        self.add_links(result.link_scores.iter().copied()) + result.bonus_garbage
+    }
+}
+
+///How a player fights back against the nuisance coming their way.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum OffsetRule {
+    ///Every link cancels nuisance as it comes, and whatever is left over is sent straight away.
+    #[default]
+    Tsu,
+    ///Every link cancels nuisance as it comes, but whatever is left over is held back,
+    /// and sent all at once as a counter-attack when the chain ends.
+    Fever,
+}
+
+///The nuisance one player has coming their way.
+///
+/// Nuisance starts off pending while the opponent's chain is still going,
+/// and is confirmed once it ends, ready to fall after the player's next move.
+/// Outgoing nuisance always cancels confirmed nuisance before pending nuisance.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct GarbageQueue {
+    pub rule: OffsetRule,
+    ///Nuisance from a chain that is still going.
+    pub pending: u64,
+    ///Nuisance that will fall after the next move.
+    pub confirmed: u64,
+    ///Nuisance held back for a counter-attack, only used by `OffsetRule::Fever`.
+    pub held: u64,
+}
+
+impl GarbageQueue {
+    pub fn new(rule: OffsetRule) -> Self {
+        GarbageQueue {
+            rule,
+            ..Default::default()
+        }
+    }
+    ///All the nuisance coming this way.
+    pub fn incoming(&self) -> u64 {
+        self.pending + self.confirmed
+    }
+    ///Nuisance sent by a link of the opponent's chain.
+    pub fn receive(&mut self, amount: u64) {
+        self.pending += amount;
+    }
+    ///The opponent's chain has ended, so their nuisance is ready to fall.
+    pub fn confirm(&mut self) {
+        self.confirmed += self.pending;
+        self.pending = 0;
+    }
+    ///Cancels incoming nuisance with `outgoing` nuisance from a link of this player's chain,
+    /// returning how much is left over to send.
+    ///
+    /// With `OffsetRule::Fever` the leftovers are held back instead, and this always returns 0.
+    pub fn offset(&mut self, outgoing: u64) -> u64 {
+        let from_confirmed = outgoing.min(self.confirmed);
+        self.confirmed -= from_confirmed;
+        let from_pending = (outgoing - from_confirmed).min(self.pending);
+        self.pending -= from_pending;
+        let excess = outgoing - from_confirmed - from_pending;
+        match self.rule {
+            OffsetRule::Tsu => excess,
+            OffsetRule::Fever => {
+                self.held += excess;
+                0
+            }
+        }
+    }
+    ///Sends the nuisance from a link of this player's chain, after cancelling what it can.
+    pub fn send(&mut self, opponent: &mut GarbageQueue, outgoing: u64) {
+        let excess = self.offset(outgoing);
+        opponent.receive(excess);
+    }
+    ///This player's chain has ended, so the counter-attack is let loose and the opponent's nuisance is confirmed.
+    pub fn end_chain(&mut self, opponent: &mut GarbageQueue) {
+        opponent.receive(core::mem::take(&mut self.held));
+        opponent.confirm();
+    }
+    ///Takes up to `max` confirmed nuisance to drop onto the board, like the 30 pieces of Tsu.
+    pub fn take_drop(&mut self, max: u64) -> u64 {
+        let dropped = self.confirmed.min(max);
+        self.confirmed -= dropped;
+        dropped
+    }
+    ///Both players send nuisance at the same moment.
+    ///
+    /// Each player cancels their own incoming nuisance first, then what's left over cancels out,
+    /// so the result is the same whichever player is passed first.
+    pub fn trade(a: &mut GarbageQueue, a_sends: u64, b: &mut GarbageQueue, b_sends: u64) {
+        let a_excess = a.offset(a_sends);
+        let b_excess = b.offset(b_sends);
+        let cancelled = a_excess.min(b_excess);
+        b.receive(a_excess - cancelled);
+        a.receive(b_excess - cancelled);
+    }
+}
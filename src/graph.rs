@@ -0,0 +1,166 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    BanishBoard, Board, PlaceBoard,
+    gravity::{Discard, GravityBoard},
+    grid::{GridTile, GridTileBoard, tile_hooks},
+    quicksim::QuickSimBoard,
+};
+
+///A board where every tile is a node, joined to its neighbors by an explicit list of edges.
+///
+/// Good for irregular layouts like rings, islands and portals.
+/// Instead of columns, gravity follows a "falls to" edge out of each node.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GraphBoard<T: GridTile> {
+    items: Vec<T>,
+    adjacency: Vec<Vec<usize>>,
+    falls_to: Vec<Option<usize>>,
+}
+
+impl<T: GridTile> GraphBoard<T> {
+    ///A board of `nodes` air tiles, with no edges at all.
+    pub fn new(nodes: usize) -> Self {
+        GraphBoard {
+            items: vec![T::air(); nodes],
+            adjacency: vec![vec![]; nodes],
+            falls_to: vec![None; nodes],
+        }
+    }
+    ///A board of `nodes` air tiles, joined by every edge in `edges` that is inside the board.
+    pub fn from_edges(nodes: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut this = Self::new(nodes);
+        for (a, b) in edges {
+            this.add_edge(a, b);
+        }
+        this
+    }
+    ///How many nodes there are.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    ///Joins `a` and `b` so that they are neighbors of each other.
+    ///
+    /// Returns `false` and does nothing if either node is outside the board.
+    pub fn add_edge(&mut self, a: usize, b: usize) -> bool {
+        if a >= self.len() || b >= self.len() {
+            return false;
+        }
+        if a != b && !self.adjacency[a].contains(&b) {
+            self.adjacency[a].push(b);
+            self.adjacency[b].push(a);
+        }
+        true
+    }
+    ///Sets where the being at `node` falls to when there is air there.
+    ///
+    /// The falls-to edges shouldn't form a loop, if they do, beings stop going around it after `len` steps.
+    /// Returns `false` and does nothing if either node is outside the board.
+    pub fn set_falls_to(&mut self, node: usize, to: Option<usize>) -> bool {
+        if node >= self.len() || to.is_some_and(|to| to >= self.len()) {
+            return false;
+        }
+        self.falls_to[node] = to;
+        true
+    }
+    pub fn falls_to(&self, node: usize) -> Option<usize> {
+        self.falls_to.get(node).copied().flatten()
+    }
+    ///Gets the tile at `node`, anything outside the board is air.
+    pub fn get(&self, node: usize) -> T {
+        self.items.get(node).copied().unwrap_or_else(T::air)
+    }
+    ///Sets the tile at `node`, does nothing outside the board.
+    pub fn set(&mut self, node: usize, to: T) {
+        if let Some(tile) = self.items.get_mut(node) {
+            *tile = to;
+        }
+    }
+    ///Moves every being one step along its falls-to edge, lowest node first.
+    fn fall_step(&mut self, changed: &mut impl Extend<usize>) -> bool {
+        let mut did_fall = false;
+        for from in 0..self.len() {
+            let tile = self.items[from];
+            if tile.is_air() || tile.is_anchored() {
+                continue;
+            }
+            if let Some(to) = self.falls_to[from]
+                && self.items[to].is_air()
+            {
+                self.items[to] = tile;
+                self.items[from] = T::air();
+                changed.extend([from, to]);
+                did_fall = true;
+            }
+        }
+        did_fall
+    }
+}
+
+impl<T: GridTile> Board for GraphBoard<T> {
+    type Handle = usize;
+
+    fn tiles(&self) -> impl Iterator<Item = usize> {
+        0..self.len()
+    }
+
+    fn neighbors(&self, handle: &usize) -> impl Iterator<Item = usize> {
+        self.adjacency.get(*handle).into_iter().flatten().copied()
+    }
+
+    tile_hooks!();
+}
+
+impl<T: GridTile> GridTileBoard for GraphBoard<T> {
+    type Tile = T;
+    fn tile(&self, handle: &usize) -> T {
+        self.get(*handle)
+    }
+    fn set_tile(&mut self, handle: &usize, tile: T) {
+        self.set(*handle, tile)
+    }
+}
+
+impl<T: GridTile> BanishBoard for GraphBoard<T> {
+    fn banish(&mut self, handle: usize) {
+        self.set(handle, T::air());
+    }
+}
+
+impl<T: GridTile> PlaceBoard for GraphBoard<T> {
+    type Being = T;
+    fn place(&mut self, handle: usize, being: T) {
+        self.set(handle, being);
+    }
+    ///A graph only knows which way is down, so the only steps are `(0, -1)` along the falls-to edge,
+    /// and `(0, 0)`, which stays put.
+    fn step(&self, &handle: &usize, by: (isize, isize)) -> Option<usize> {
+        match by {
+            (0, 0) => (handle < self.len()).then_some(handle),
+            (0, -1) => self.falls_to(handle),
+            _ => None,
+        }
+    }
+}
+
+impl<T: GridTile> GravityBoard for GraphBoard<T> {
+    fn fall(&mut self) -> bool {
+        self.fall_tracked(&mut Discard)
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool {
+        let mut did_fall = false;
+        for _ in 0..self.len() {
+            if !self.fall_step(changed) {
+                break;
+            }
+            did_fall = true;
+        }
+        did_fall
+    }
+}
+
+impl<T: GridTile> QuickSimBoard for GraphBoard<T> {}
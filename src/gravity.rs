@@ -1,46 +1,119 @@
+use alloc::vec::Vec;
+
 use crate::Board;
 
 pub trait GravityBoard {
     fn fall(&mut self) -> bool;
+    ///Like `fall`, but also records the handles of every tile that changed into `changed`.
+    ///
+    /// Used to regroup incrementally with `Board::owanimo_regroup`.
+    /// The default implementation conservatively marks every tile as changed if anything fell.
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool
+    where
+        Self: Board,
+    {
+        let did_fall = self.fall();
+        if did_fall {
+            changed.extend(self.tiles());
+        }
+        did_fall
+    }
+}
+
+///A board where beings can fall along more than one axis, such as a 3D grid.
+pub trait AxisGravityBoard: Board {
+    type Axis: Copy;
+    ///Lets every being fall along `axis`, recording the handles of every tile that changed into `changed`.
+    fn fall_along(&mut self, axis: Self::Axis, changed: &mut impl Extend<Self::Handle>) -> bool;
+}
+
+///An `Extend` that throws everything away, for when the changed tiles don't matter.
+pub struct Discard;
+
+impl<T> Extend<T> for Discard {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(drop);
+    }
 }
 
 pub trait AutoGravityBoard: Board {
     fn is_air(&self, handle: Self::Handle) -> bool;
+    ///Is the being at `handle` held in place, like an iron block? Beings above it land on it instead of falling past.
+    fn anchored(&self, _handle: Self::Handle) -> bool {
+        false
+    }
     fn mutate_columns(&mut self, mutater: impl FnMut(&Self, &mut [Self::Handle]));
 }
 
+///Lets every part of a column between anchored beings fall on its own.
+fn fall_segments<T: AutoGravityBoard>(this: &T, col: &mut [T::Handle]) -> bool {
+    let mut did_fall = false;
+    for segment in col.split_mut(|&handle| this.anchored(handle)) {
+        if !segment.is_empty() {
+            did_fall |= fall_column(this, segment);
+        }
+    }
+    did_fall
+}
+
+fn fall_column<T: AutoGravityBoard>(this: &T, col: &mut [T::Handle]) -> bool {
+    let mut did_fall = false;
+    let mut index = 0;
+    'outer: loop {
+        let cursor_a = index;
+        //cursor starts at bottom, assumes itself to be air,
+        while this.is_air(col[index]) {
+            index += 1;
+            if index == col.len() {
+                break 'outer;
+            };
+        }
+        let cursor_b = index;
+        //now find how many are not
+        while !this.is_air(col[index]) {
+            index += 1;
+            if index == col.len() {
+                break;
+            }
+        }
+        if cursor_b != cursor_a {
+            did_fall = true;
+        }
+        //then rotate cursor_a..index leftwards to push the air bubbles to the top and repeat
+        col[cursor_a..index].rotate_left(cursor_b - cursor_a);
+        //and rotate the index too
+        index -= cursor_b - cursor_a;
+        if index == col.len() {
+            break;
+        }
+    }
+    did_fall
+}
+
 impl<T: AutoGravityBoard> GravityBoard for T {
     fn fall(&mut self) -> bool {
         let mut did_fall = false;
         self.mutate_columns(|this, col| {
-            let mut index = 0;
-            'outer: loop {
-                let cursor_a = index;
-                //cursor starts at bottom, assumes itself to be air,
-                while this.is_air(col[index]) {
-                    index += 1;
-                    if index == col.len() {
-                        break 'outer;
-                    };
-                }
-                let cursor_b = index;
-                //now find how many are not
-                while !this.is_air(col[index]) {
-                    index += 1;
-                    if index == col.len() {
-                        break;
-                    }
-                }
-                if cursor_b != cursor_a {
-                    did_fall = true;
-                }
-                //then rotate cursor_a..index leftwards to push the air bubbles to the top and repeat
-                col[cursor_a..index].rotate_left(cursor_b - cursor_a);
-                //and rotate the index too
-                index -= cursor_b - cursor_a;
-                if index == col.len() {
-                    break;
-                }
+            did_fall |= fall_segments(this, col);
+        });
+        did_fall
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool
+    where
+        Self: Board,
+    {
+        let mut did_fall = false;
+        self.mutate_columns(|this, col| {
+            let original: Vec<_> = col.to_vec();
+            if fall_segments(this, col) {
+                did_fall = true;
+                changed.extend(
+                    original
+                        .into_iter()
+                        .zip(col.iter())
+                        .filter(|(before, after)| before != *after)
+                        .map(|(before, _)| before),
+                );
             }
         });
         did_fall
@@ -0,0 +1,370 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    BanishBoard, Board, BoardHandle, ParseError, PlaceBoard,
+    gravity::{AutoGravityBoard, GravityBoard},
+    parse_rows,
+    quicksim::QuickSimBoard,
+    rng::Rng,
+    standard::{ColorBoard, GroupFromColorBoard, NuisanceBoard, PointBoard, SunBoard},
+};
+
+///A being that can live in a `GridBoard`.
+pub trait GridTile: Copy {
+    type Color: BoardHandle;
+    ///The empty tile, left behind when a being is banished.
+    fn air() -> Self;
+    fn is_air(&self) -> bool;
+    ///Will this being be sucked into the otherworld if a neighboring being was banished?
+    fn is_nuisance(&self) -> bool;
+    ///How many more hits this hard nuisance can take before it turns into regular nuisance, see `NuisanceBoard::hit_points`.
+    fn hit_points(&self) -> u32 {
+        0
+    }
+    ///Takes `hits` hit points away from this hard nuisance, turning it into regular nuisance once it has none left.
+    fn damage(&mut self, _hits: u32) {}
+    ///How many points this being is worth when it's banished, see `PointBoard`.
+    fn points(&self) -> u64 {
+        0
+    }
+    ///Is this a Sun piece? See `SunBoard`.
+    fn is_sun(&self) -> bool {
+        false
+    }
+    ///Is this an indestructible block? See `Board::block`.
+    fn is_block(&self) -> bool {
+        false
+    }
+    ///Does this being stay where it is instead of falling? See `AutoGravityBoard::anchored`.
+    fn is_anchored(&self) -> bool {
+        false
+    }
+    ///Is this being a bomb, and if so, what does its blast look like? See `BanishBoard::blast`.
+    fn blast(&self) -> Option<Blast> {
+        None
+    }
+    ///Wildcards should have no color.
+    fn color(&self) -> Option<Self::Color>;
+    ///Does this being join every group it touches? See `Board::wildcard`.
+    fn is_wildcard(&self) -> bool {
+        false
+    }
+    ///Does this being connect with `other`? By default, beings of the same color connect,
+    /// and wildcards connect with every colored being and each other.
+    fn connects_with(&self, other: &Self) -> bool {
+        match (self.is_wildcard(), other.is_wildcard()) {
+            (true, true) => true,
+            (true, false) => other.color().is_some(),
+            (false, true) => self.color().is_some(),
+            (false, false) => match (self.color(), other.color()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+///A board where every being is a `GridTile`.
+///
+/// Any `GridTileBoard` gets `ColorBoard`, `GroupFromColorBoard`, `NuisanceBoard`, `PointBoard` and `SunBoard` from its tiles,
+/// and `tile_hooks!` fills in the `Board` methods that only look at the tiles.
+pub trait GridTileBoard: Board {
+    type Tile: GridTile;
+    ///The being at `handle`.
+    fn tile(&self, handle: &Self::Handle) -> Self::Tile;
+    ///Puts `tile` at `handle`.
+    fn set_tile(&mut self, handle: &Self::Handle, tile: Self::Tile);
+}
+
+impl<B: GridTileBoard> ColorBoard for B {
+    type Color = <B::Tile as GridTile>::Color;
+    fn color(&self, handle: &Self::Handle) -> Option<Self::Color> {
+        self.tile(handle).color()
+    }
+}
+
+impl<B: GridTileBoard> GroupFromColorBoard for B {}
+
+impl<B: GridTileBoard> NuisanceBoard for B {
+    fn nuisance(&self, handle: &Self::Handle) -> bool {
+        self.tile(handle).is_nuisance()
+    }
+    fn hit_points(&self, handle: &Self::Handle) -> u32 {
+        self.tile(handle).hit_points()
+    }
+    fn damage(&mut self, handle: &Self::Handle, hits: u32) {
+        let mut tile = self.tile(handle);
+        tile.damage(hits);
+        self.set_tile(handle, tile);
+    }
+}
+
+impl<B: GridTileBoard> PointBoard for B {
+    fn points(&self, handle: &Self::Handle) -> u64 {
+        self.tile(handle).points()
+    }
+}
+
+impl<B: GridTileBoard> SunBoard for B {
+    fn sun(&self, handle: &Self::Handle) -> bool {
+        self.tile(handle).is_sun()
+    }
+}
+
+///Fills in the `Board` methods of a `GridTileBoard` that only look at its tiles, use it inside `impl Board`.
+macro_rules! tile_hooks {
+    () => {
+        fn connects(&self, a: &Self::Handle, b: &Self::Handle) -> bool {
+            $crate::grid::GridTile::connects_with(
+                &$crate::grid::GridTileBoard::tile(self, a),
+                &$crate::grid::GridTileBoard::tile(self, b),
+            )
+        }
+
+        fn wildcard(&self, handle: &Self::Handle) -> bool {
+            $crate::grid::GridTile::is_wildcard(&$crate::grid::GridTileBoard::tile(self, handle))
+        }
+
+        fn block(&self, handle: &Self::Handle) -> bool {
+            $crate::grid::GridTile::is_block(&$crate::grid::GridTileBoard::tile(self, handle))
+        }
+
+        fn air(&self, handle: &Self::Handle) -> bool {
+            $crate::grid::GridTile::is_air(&$crate::grid::GridTileBoard::tile(self, handle))
+        }
+    };
+}
+pub(crate) use tile_hooks;
+
+///The shape of the blast when a bomb on a `GridBoard` is banished.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Blast {
+    ///Every tile at most this far away, as the crow flies.
+    Radius(usize),
+    ///Every tile in the same row or column.
+    Cross,
+}
+
+///A board laid out as a 2D Cartesian Grid, with `(0, 0)` at the bottom left.
+///
+/// Adapters like `wrap::Wrapping` work on top of any `GridTileBoard` like this.
+pub trait CartesianBoard: Board<Handle = (usize, usize)> {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+}
+
+///A grid board that nuisance can be dropped onto.
+pub trait NuisanceDropBoard: CartesianBoard + AutoGravityBoard {
+    ///Puts a piece of nuisance at `handle`.
+    fn put_nuisance(&mut self, handle: (usize, usize));
+    ///Drops `amount` nuisance onto the board, returning how much of it fit.
+    ///
+    /// Full rows are spread evenly over every column, and the rest go into columns picked by `rng`,
+    /// never more than one to a column. Nuisance only lands on the air at the top of each column.
+    fn drop_nuisance(&mut self, amount: u64, rng: &mut impl Rng) -> u64
+    where
+        Self: Sized,
+    {
+        let width = self.width();
+        if width == 0 {
+            return 0;
+        }
+        let mut per_column = vec![amount / width as u64; width];
+        //shuffle just enough of the columns to pick the leftovers
+        let mut columns: Vec<usize> = (0..width).collect();
+        for i in 0..(amount % width as u64) as usize {
+            let j = i + rng.below((width - i) as u64) as usize;
+            columns.swap(i, j);
+            per_column[columns[i]] += 1;
+        }
+        let mut placed = 0;
+        for (x, mut left) in per_column.into_iter().enumerate() {
+            for y in (0..self.height()).rev() {
+                if left == 0 || !self.is_air((x, y)) {
+                    break;
+                }
+                self.put_nuisance((x, y));
+                left -= 1;
+                placed += 1;
+            }
+        }
+        self.fall();
+        placed
+    }
+}
+
+///A tile that can be made into nuisance, so that `GridBoard` can drop it with `NuisanceDropBoard`.
+pub trait NuisanceTile: GridTile {
+    fn nuisance() -> Self;
+}
+
+///A `W` wide and `H` tall 2D Cartesian Grid, with `(0, 0)` at the bottom left.
+///
+/// Implements every trait needed for `quick_sim` out of the box.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GridBoard<T: GridTile, const W: usize, const H: usize> {
+    ///The tiles, indexed by `items[y][x]`.
+    pub items: [[T; W]; H],
+}
+
+impl<T: GridTile, const W: usize, const H: usize> Default for GridBoard<T, W, H> {
+    fn default() -> Self {
+        GridBoard {
+            items: [[T::air(); W]; H],
+        }
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> GridBoard<T, W, H> {
+    pub const WIDTH: usize = W;
+    pub const HEIGHT: usize = H;
+    ///A board filled with air.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    ///Gets the tile at `(x, y)`, anything outside the board is air.
+    pub fn get(&self, (x, y): (usize, usize)) -> T {
+        self.items
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or_else(T::air)
+    }
+    ///Sets the tile at `(x, y)`, does nothing outside the board.
+    pub fn set(&mut self, (x, y): (usize, usize), to: T) {
+        if let Some(tile) = self.items.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *tile = to;
+        }
+    }
+    ///Copies out column `x`, from the bottom up.
+    pub fn getcol(&self, x: usize) -> [T; H] {
+        core::array::from_fn(|y| self.get((x, y)))
+    }
+    ///Replaces column `x`, from the bottom up.
+    pub fn setcol(&mut self, x: usize, col: [T; H]) {
+        for (y, tile) in col.into_iter().enumerate() {
+            self.set((x, y), tile);
+        }
+    }
+    ///Is every tile on the board air?
+    pub fn is_empty(&self) -> bool {
+        self.items.iter().flatten().all(T::is_air)
+    }
+}
+
+///Parses rows from top to bottom, every symbol is turned into a tile with `T::try_from`.
+impl<T: GridTile + TryFrom<char>, const W: usize, const H: usize> core::str::FromStr
+    for GridBoard<T, W, H>
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut this = Self::default();
+        parse_rows(s, W, H, |at, symbol| {
+            let tile = T::try_from(symbol).map_err(|_| ParseError::UnknownSymbol { at, symbol })?;
+            this.set(at, tile);
+            Ok(())
+        })?;
+        Ok(this)
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> Board for GridBoard<T, W, H> {
+    type Handle = (usize, usize);
+
+    fn tiles(&self) -> impl Iterator<Item = Self::Handle> {
+        (0..W).flat_map(|x| (0..H).map(move |y| (x, y)))
+    }
+
+    fn neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        let (x, y) = *handle;
+        [
+            (x > 0).then(|| (x - 1, y)),
+            (y > 0).then(|| (x, y - 1)),
+            (x + 1 < W).then_some((x + 1, y)),
+            (y + 1 < H).then_some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    tile_hooks!();
+}
+
+impl<T: GridTile, const W: usize, const H: usize> GridTileBoard for GridBoard<T, W, H> {
+    type Tile = T;
+    fn tile(&self, handle: &Self::Handle) -> T {
+        self.get(*handle)
+    }
+    fn set_tile(&mut self, handle: &Self::Handle, tile: T) {
+        self.set(*handle, tile)
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> CartesianBoard for GridBoard<T, W, H> {
+    fn width(&self) -> usize {
+        W
+    }
+    fn height(&self) -> usize {
+        H
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> BanishBoard for GridBoard<T, W, H> {
+    fn banish(&mut self, handle: Self::Handle) {
+        self.set(handle, T::air());
+    }
+    fn blast(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        let (x, y) = *handle;
+        self.get(*handle)
+            .blast()
+            .into_iter()
+            .flat_map(move |blast| {
+                self.tiles().filter(move |&(tx, ty)| {
+                    let caught = match blast {
+                        Blast::Radius(r) => x.abs_diff(tx).pow(2) + y.abs_diff(ty).pow(2) <= r * r,
+                        Blast::Cross => tx == x || ty == y,
+                    };
+                    caught && (tx, ty) != (x, y) && !self.get((tx, ty)).is_air()
+                })
+            })
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> PlaceBoard for GridBoard<T, W, H> {
+    type Being = T;
+    fn place(&mut self, handle: Self::Handle, being: T) {
+        self.set(handle, being);
+    }
+    fn step(&self, &(x, y): &Self::Handle, (dx, dy): (isize, isize)) -> Option<Self::Handle> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < W)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < H)?;
+        Some((x, y))
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> AutoGravityBoard for GridBoard<T, W, H> {
+    fn is_air(&self, handle: Self::Handle) -> bool {
+        self.get(handle).is_air()
+    }
+    fn anchored(&self, handle: Self::Handle) -> bool {
+        self.get(handle).is_anchored()
+    }
+    fn mutate_columns(&mut self, mut mutater: impl FnMut(&Self, &mut [Self::Handle])) {
+        for x in 0..W {
+            let mut col: [Self::Handle; H] = core::array::from_fn(|y| (x, y));
+            mutater(self, &mut col);
+            let tiles_original = self.getcol(x);
+            self.setcol(x, col.map(|(_, y)| tiles_original[y]));
+        }
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> QuickSimBoard for GridBoard<T, W, H> {}
+
+impl<T: NuisanceTile, const W: usize, const H: usize> NuisanceDropBoard for GridBoard<T, W, H> {
+    fn put_nuisance(&mut self, handle: (usize, usize)) {
+        self.set(handle, T::nuisance());
+    }
+}
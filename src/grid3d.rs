@@ -0,0 +1,232 @@
+use crate::{
+    BanishBoard, Board, PlaceBoard,
+    gravity::{AxisGravityBoard, Discard, GravityBoard},
+    grid::{GridTile, GridTileBoard, tile_hooks},
+    quicksim::QuickSimBoard,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+///Which of the 26 cubes around a cube count as its neighbors.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum Connectivity {
+    ///The 6 cubes sharing a face.
+    #[default]
+    Faces,
+    ///The 18 cubes sharing a face or an edge.
+    Edges,
+    ///All 26 cubes sharing a face, an edge or a corner.
+    Corners,
+}
+
+impl Connectivity {
+    ///How many coordinates a neighbor may differ in.
+    fn reach(self) -> usize {
+        match self {
+            Connectivity::Faces => 1,
+            Connectivity::Edges => 2,
+            Connectivity::Corners => 3,
+        }
+    }
+}
+
+///An `X` by `Y` by `Z` grid of cubes, for the most advanced of mages.
+///
+/// Beings fall towards 0 along the `gravity` axis.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Grid3D<T: GridTile, const X: usize, const Y: usize, const Z: usize> {
+    ///The tiles, indexed by `items[z][y][x]`.
+    pub items: [[[T; X]; Y]; Z],
+    pub connectivity: Connectivity,
+    pub gravity: Axis,
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> Default for Grid3D<T, X, Y, Z> {
+    fn default() -> Self {
+        Grid3D {
+            items: [[[T::air(); X]; Y]; Z],
+            connectivity: Connectivity::default(),
+            gravity: Axis::Z,
+        }
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> Grid3D<T, X, Y, Z> {
+    ///A board filled with air.
+    pub fn new(connectivity: Connectivity, gravity: Axis) -> Self {
+        Grid3D {
+            connectivity,
+            gravity,
+            ..Self::default()
+        }
+    }
+    ///Gets the tile at `(x, y, z)`, anything outside the board is air.
+    pub fn get(&self, (x, y, z): (usize, usize, usize)) -> T {
+        self.items
+            .get(z)
+            .and_then(|plane| plane.get(y)?.get(x))
+            .copied()
+            .unwrap_or_else(T::air)
+    }
+    ///Sets the tile at `(x, y, z)`, does nothing outside the board.
+    pub fn set(&mut self, (x, y, z): (usize, usize, usize), to: T) {
+        if let Some(tile) = self
+            .items
+            .get_mut(z)
+            .and_then(|plane| plane.get_mut(y)?.get_mut(x))
+        {
+            *tile = to;
+        }
+    }
+    ///Is every tile on the board air?
+    pub fn is_empty(&self) -> bool {
+        self.items.iter().flatten().flatten().all(T::is_air)
+    }
+    ///The handles along `axis` that go through `(a, b)`, which are the other two coordinates in `xyz` order.
+    fn line(axis: Axis, a: usize, b: usize) -> impl Iterator<Item = (usize, usize, usize)> {
+        let len = match axis {
+            Axis::X => X,
+            Axis::Y => Y,
+            Axis::Z => Z,
+        };
+        (0..len).map(move |i| match axis {
+            Axis::X => (i, a, b),
+            Axis::Y => (a, i, b),
+            Axis::Z => (a, b, i),
+        })
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> Board for Grid3D<T, X, Y, Z> {
+    type Handle = (usize, usize, usize);
+
+    fn tiles(&self) -> impl Iterator<Item = Self::Handle> {
+        (0..Z).flat_map(|z| (0..Y).flat_map(move |y| (0..X).map(move |x| (x, y, z))))
+    }
+
+    fn neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        let (x, y, z) = *handle;
+        let reach = self.connectivity.reach();
+        let steps = |at: usize, len: usize| {
+            [
+                at.checked_sub(1).map(|to| (to, 1)),
+                Some((at, 0)),
+                (at + 1 < len).then_some((at + 1, 1)),
+            ]
+            .into_iter()
+            .flatten()
+        };
+        steps(z, Z).flat_map(move |(nz, dz)| {
+            steps(y, Y).flat_map(move |(ny, dy)| {
+                steps(x, X).filter_map(move |(nx, dx)| {
+                    let moved = dx + dy + dz;
+                    (moved > 0 && moved <= reach).then_some((nx, ny, nz))
+                })
+            })
+        })
+    }
+
+    tile_hooks!();
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> GridTileBoard
+    for Grid3D<T, X, Y, Z>
+{
+    type Tile = T;
+    fn tile(&self, handle: &Self::Handle) -> T {
+        self.get(*handle)
+    }
+    fn set_tile(&mut self, handle: &Self::Handle, tile: T) {
+        self.set(*handle, tile)
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> BanishBoard
+    for Grid3D<T, X, Y, Z>
+{
+    fn banish(&mut self, handle: Self::Handle) {
+        self.set(handle, T::air());
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> PlaceBoard
+    for Grid3D<T, X, Y, Z>
+{
+    type Being = T;
+    fn place(&mut self, handle: Self::Handle, being: T) {
+        self.set(handle, being);
+    }
+    ///Steps go up against `gravity`, and sideways along the first of the other two axes, in `xyz` order.
+    fn step(&self, &(x, y, z): &Self::Handle, (dx, dy): (isize, isize)) -> Option<Self::Handle> {
+        let (dx, dy, dz) = match self.gravity {
+            Axis::X => (dy, dx, 0),
+            Axis::Y => (dx, dy, 0),
+            Axis::Z => (dx, 0, dy),
+        };
+        let x = x.checked_add_signed(dx).filter(|&x| x < X)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < Y)?;
+        let z = z.checked_add_signed(dz).filter(|&z| z < Z)?;
+        Some((x, y, z))
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> AxisGravityBoard
+    for Grid3D<T, X, Y, Z>
+{
+    type Axis = Axis;
+    fn fall_along(&mut self, axis: Axis, changed: &mut impl Extend<Self::Handle>) -> bool {
+        let (a_len, b_len) = match axis {
+            Axis::X => (Y, Z),
+            Axis::Y => (X, Z),
+            Axis::Z => (X, Y),
+        };
+        let mut did_fall = false;
+        for a in 0..a_len {
+            for b in 0..b_len {
+                let mut landing = 0;
+                for (i, from) in Self::line(axis, a, b).enumerate() {
+                    let tile = self.get(from);
+                    if tile.is_air() {
+                        continue;
+                    }
+                    if tile.is_anchored() {
+                        //beings above this one land on it
+                        landing = i + 1;
+                        continue;
+                    }
+                    //there's always a landing spot at or below a being
+                    let to = Self::line(axis, a, b).nth(landing).unwrap_or(from);
+                    landing += 1;
+                    if to != from {
+                        self.set(to, tile);
+                        self.set(from, T::air());
+                        changed.extend([from, to]);
+                        did_fall = true;
+                    }
+                }
+            }
+        }
+        did_fall
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> GravityBoard
+    for Grid3D<T, X, Y, Z>
+{
+    fn fall(&mut self) -> bool {
+        self.fall_along(self.gravity, &mut Discard)
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool {
+        self.fall_along(self.gravity, changed)
+    }
+}
+
+impl<T: GridTile, const X: usize, const Y: usize, const Z: usize> QuickSimBoard
+    for Grid3D<T, X, Y, Z>
+{
+}
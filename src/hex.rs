@@ -0,0 +1,235 @@
+use crate::{
+    BanishBoard, Board, ParseError, PlaceBoard,
+    gravity::GravityBoard,
+    grid::{GridTile, GridTileBoard, tile_hooks},
+    parse_rows,
+    quicksim::QuickSimBoard,
+};
+
+///A hexagon in axial coordinates, on a lattice of flat-topped hexagons.
+///
+/// `q` counts columns to the right, and `r` counts up the column,
+/// so `(0, -1)` is straight down, `(-1, 0)` is down and to the left and `(1, -1)` is down and to the right.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    ///The six neighbors of `(0, 0)`, starting straight up and going clockwise.
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(0, 1),
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+    ];
+    pub const DOWN: Hex = Hex::new(0, -1);
+    pub const DOWN_LEFT: Hex = Hex::new(-1, 0);
+    pub const DOWN_RIGHT: Hex = Hex::new(1, -1);
+
+    pub const fn new(q: i32, r: i32) -> Hex {
+        Hex { q, r }
+    }
+    ///Converts from the column `x` and row `y` of a rectangular board,
+    /// where odd columns sit half a hexagon higher than even ones.
+    pub fn from_offset((x, y): (usize, usize)) -> Hex {
+        let q = x as i32;
+        Hex::new(q, y as i32 - q / 2)
+    }
+    ///Converts to the column and row of a rectangular board, see `from_offset`.
+    pub fn to_offset(self) -> Option<(usize, usize)> {
+        let y = self.r + self.q / 2;
+        (self.q >= 0 && y >= 0).then_some((self.q as usize, y as usize))
+    }
+}
+
+impl core::ops::Add for Hex {
+    type Output = Hex;
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+///What "down" means for the beings on a `HexBoard`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum HexGravity {
+    ///Beings fall straight down their column, like on a square grid.
+    #[default]
+    Straight,
+    ///Beings fall straight down, and when that's blocked they roll into the notch down and to the left,
+    /// or failing that down and to the right.
+    Slide,
+}
+
+///A `W` columns wide and `H` rows tall board of flat-topped hexagons, where every tile has six neighbors.
+///
+/// Handles are axial `Hex` coordinates, see `Hex::from_offset` for how they map to the rows and columns.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct HexBoard<T: GridTile, const W: usize, const H: usize> {
+    ///The tiles, indexed by `items[y][x]` in offset coordinates.
+    pub items: [[T; W]; H],
+    pub gravity: HexGravity,
+}
+
+impl<T: GridTile, const W: usize, const H: usize> Default for HexBoard<T, W, H> {
+    fn default() -> Self {
+        HexBoard {
+            items: [[T::air(); W]; H],
+            gravity: HexGravity::default(),
+        }
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> HexBoard<T, W, H> {
+    ///A board filled with air.
+    pub fn new(gravity: HexGravity) -> Self {
+        HexBoard {
+            gravity,
+            ..Self::default()
+        }
+    }
+    ///Is the hexagon inside the board?
+    pub fn contains(&self, hex: Hex) -> bool {
+        hex.to_offset().is_some_and(|(x, y)| x < W && y < H)
+    }
+    ///Gets the tile at `hex`, anything outside the board is air.
+    pub fn get(&self, hex: Hex) -> T {
+        hex.to_offset()
+            .and_then(|(x, y)| self.items.get(y)?.get(x))
+            .copied()
+            .unwrap_or_else(T::air)
+    }
+    ///Sets the tile at `hex`, does nothing outside the board.
+    pub fn set(&mut self, hex: Hex, to: T) {
+        if let Some(tile) = hex
+            .to_offset()
+            .and_then(|(x, y)| self.items.get_mut(y)?.get_mut(x))
+        {
+            *tile = to;
+        }
+    }
+    ///Where would the being at `hex` fall to next, if anywhere?
+    fn next_fall(&self, hex: Hex) -> Option<Hex> {
+        let free = |to: Hex| self.contains(to) && self.get(to).is_air();
+        let directions: &[Hex] = match self.gravity {
+            HexGravity::Straight => &[Hex::DOWN],
+            HexGravity::Slide => &[Hex::DOWN, Hex::DOWN_LEFT, Hex::DOWN_RIGHT],
+        };
+        directions.iter().map(|&d| hex + d).find(|&to| free(to))
+    }
+    ///Moves every being one step down, bottom row first, calling `moved` with the handles of each move.
+    fn fall_step(&mut self, mut moved: impl FnMut(Hex, Hex)) -> bool {
+        let mut did_fall = false;
+        for y in 0..H {
+            for x in 0..W {
+                let from = Hex::from_offset((x, y));
+                let tile = self.get(from);
+                if tile.is_air() || tile.is_anchored() {
+                    continue;
+                }
+                if let Some(to) = self.next_fall(from) {
+                    self.set(to, tile);
+                    self.set(from, T::air());
+                    moved(from, to);
+                    did_fall = true;
+                }
+            }
+        }
+        did_fall
+    }
+}
+
+///Parses rows from top to bottom in offset coordinates, every symbol is turned into a tile with `T::try_from`.
+impl<T: GridTile + TryFrom<char>, const W: usize, const H: usize> core::str::FromStr
+    for HexBoard<T, W, H>
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut this = Self::default();
+        parse_rows(s, W, H, |at, symbol| {
+            let tile = T::try_from(symbol).map_err(|_| ParseError::UnknownSymbol { at, symbol })?;
+            this.set(Hex::from_offset(at), tile);
+            Ok(())
+        })?;
+        Ok(this)
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> Board for HexBoard<T, W, H> {
+    type Handle = Hex;
+
+    fn tiles(&self) -> impl Iterator<Item = Hex> {
+        (0..W).flat_map(|x| (0..H).map(move |y| Hex::from_offset((x, y))))
+    }
+
+    /// On a hexagonal grid, this is the six tiles marked `X` around the handle marked `O`:
+    /// ```text
+    ///   X
+    /// X   X
+    ///   O
+    /// X   X
+    ///   X
+    /// ```
+    fn neighbors(&self, handle: &Hex) -> impl Iterator<Item = Hex> {
+        let handle = *handle;
+        Hex::DIRECTIONS
+            .into_iter()
+            .map(move |d| handle + d)
+            .filter(|&h| self.contains(h))
+    }
+
+    tile_hooks!();
+}
+
+impl<T: GridTile, const W: usize, const H: usize> GridTileBoard for HexBoard<T, W, H> {
+    type Tile = T;
+    fn tile(&self, handle: &Hex) -> T {
+        self.get(*handle)
+    }
+    fn set_tile(&mut self, handle: &Hex, tile: T) {
+        self.set(*handle, tile)
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> BanishBoard for HexBoard<T, W, H> {
+    fn banish(&mut self, handle: Hex) {
+        self.set(handle, T::air());
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> PlaceBoard for HexBoard<T, W, H> {
+    type Being = T;
+    fn place(&mut self, handle: Hex, being: T) {
+        self.set(handle, being);
+    }
+    ///Steps go by column and row, see `Hex::from_offset`, so going sideways keeps to the same row.
+    fn step(&self, handle: &Hex, (dx, dy): (isize, isize)) -> Option<Hex> {
+        let (x, y) = handle.to_offset()?;
+        let x = x.checked_add_signed(dx).filter(|&x| x < W)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < H)?;
+        Some(Hex::from_offset((x, y)))
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> GravityBoard for HexBoard<T, W, H> {
+    fn fall(&mut self) -> bool {
+        let mut did_fall = false;
+        while self.fall_step(|_, _| {}) {
+            did_fall = true;
+        }
+        did_fall
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool {
+        let mut did_fall = false;
+        while self.fall_step(|from, to| changed.extend([from, to])) {
+            did_fall = true;
+        }
+        did_fall
+    }
+}
+
+impl<T: GridTile, const W: usize, const H: usize> QuickSimBoard for HexBoard<T, W, H> {}
@@ -9,11 +9,62 @@ pub mod gravity;
 #[cfg(feature = "quicksim")]
 pub mod quicksim;
 
+#[cfg(feature = "bitboard")]
+pub mod bitboard;
+
+#[cfg(feature = "grid")]
+pub mod grid;
+
+#[cfg(feature = "hex")]
+pub mod hex;
+
+#[cfg(feature = "grid3d")]
+pub mod grid3d;
+
+#[cfg(feature = "graph")]
+pub mod graph;
+
+#[cfg(feature = "wrap")]
+pub mod wrap;
+
+#[cfg(feature = "lines")]
+pub mod lines;
+
+#[cfg(feature = "connect")]
+pub mod connect;
+
+#[cfg(feature = "garbage")]
+pub mod garbage;
+
+#[cfg(feature = "fever")]
+pub mod fever;
+
+#[cfg(feature = "pieces")]
+pub mod pieces;
+
+#[cfg(feature = "control")]
+pub mod control;
+
+#[cfg(feature = "dense")]
+pub mod dense;
+
+#[cfg(feature = "pop")]
+pub mod pop;
+
+#[cfg(feature = "rng")]
+pub mod rng;
+
+//`owanimo_grouper` is built on this, so it's always there.
+pub mod unionfind;
+
 extern crate alloc;
 
+use alloc::borrow::Cow;
 use alloc::vec;
 use alloc::vec::Vec;
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
+
+use unionfind::DisjointSet;
 
 pub trait BoardHandle: Copy + Clone + core::hash::Hash + Eq {}
 
@@ -39,17 +90,203 @@ pub trait Board {
     fn neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle>;
     /// Do the tiles in the handles for `a` and `b` connect?
     fn connects(&self, a: &Self::Handle, b: &Self::Handle) -> bool;
+    ///The tiles that can drag the nuisance at `handle` into the otherworld with them,
+    /// which are the same as `neighbors` unless the board says otherwise.
+    fn nuisance_neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.neighbors(handle)
+    }
+    ///Is the being at `handle` a wildcard, like a rainbow being?
+    ///
+    /// A wildcard joins every group it connects to, without joining those groups to each other,
+    /// so one wildcard can end up in a red group and a blue group at the same time.
+    fn wildcard(&self, _handle: &Self::Handle) -> bool {
+        false
+    }
+    ///Is the being at `handle` an indestructible block, like stone or iron?
+    ///
+    /// Blocks never join a group, and are never popped or dragged along as nuisance.
+    fn block(&self, _handle: &Self::Handle) -> bool {
+        false
+    }
+    ///Is there no being at `handle` at all?
+    fn air(&self, _handle: &Self::Handle) -> bool {
+        false
+    }
+    ///Has every being on the board been banished? This is an All Clear.
+    fn all_clear(&self) -> bool {
+        self.tiles().all(|handle| self.air(&handle))
+    }
     ///The first part of the Owanimo spell, finds groups of beings on a board
     ///To get the second part of the spell, do `groups.as_ref().owanimo_pop()`
+    ///
+    /// Uses a disjoint-set forest, so it runs in near-linear time in the number of tiles.
     fn owanimo_grouper(&self) -> Groups<Self::Handle> {
+        self.owanimo_grouper_in()
+    }
+    ///Like `owanimo_grouper`, but stores the groups in any `GroupSet`, such as a `dense::BitSet`.
+    fn owanimo_grouper_in<S: GroupSet<Self::Handle>>(&self) -> Groups<Self::Handle, S> {
+        let tiles: Vec<Self::Handle> = self.tiles().collect();
+        //every tile starts off in a group of its own, so number the tiles with those groups
+        let mut index = S::Index::default();
+        for (i, &tile) in tiles.iter().enumerate() {
+            index.insert(tile, GroupId(i));
+        }
+        let wild: Vec<bool> = tiles.iter().map(|tile| self.wildcard(tile)).collect();
+        //blocks stay in groups of their own, whatever `connects` says
+        let block: Vec<bool> = tiles.iter().map(|tile| self.block(tile)).collect();
+        //wildcards only join up with each other here, so they never bridge two groups
+        let mut set = DisjointSet::new(tiles.len());
+        for (i, tile) in tiles.iter().enumerate().filter(|&(i, _)| !block[i]) {
+            for neighbor in self.neighbors(tile) {
+                if let Some(GroupId(j)) = index.get(&neighbor)
+                    && !block[j]
+                    && wild[i] == wild[j]
+                    && self.connects(tile, &neighbor)
+                {
+                    set.union(i, j);
+                }
+            }
+        }
+        //the tiles each clump of wildcards touches, by the root of the clump
+        let mut touching: Vec<Vec<usize>> = vec![vec![]; tiles.len()];
+        for (i, tile) in tiles
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| wild[i] && !block[i])
+        {
+            for neighbor in self.neighbors(tile) {
+                if let Some(GroupId(j)) = index.get(&neighbor)
+                    && !wild[j]
+                    && !block[j]
+                    && self.connects(tile, &neighbor)
+                {
+                    let root = set.find(i);
+                    touching[root].push(j);
+                }
+            }
+        }
+        //groups that connect through a clump of wildcards are one group, like two reds either side of a rainbow
+        for touched in &touching {
+            for (k, &a) in touched.iter().enumerate() {
+                for &b in &touched[k + 1..] {
+                    if self.connects(&tiles[a], &tiles[b]) {
+                        set.union(a, b);
+                    }
+                }
+            }
+        }
+        let mut slots: Vec<Option<usize>> = vec![None; tiles.len()];
+        let mut groups: Vec<S> = vec![];
+        let mut wildcards = vec![];
+        for (i, &tile) in tiles.iter().enumerate() {
+            let root = set.find(i);
+            if wild[i] && !touching[root].is_empty() {
+                wildcards.push((i, root));
+                continue;
+            }
+            match slots[root] {
+                Some(slot) => {
+                    groups[slot].insert(tile);
+                }
+                None => {
+                    slots[root] = Some(groups.len());
+                    groups.push([tile].into_iter().collect());
+                }
+            }
+        }
+        for (i, clump) in wildcards {
+            for &j in &touching[clump] {
+                if let Some(slot) = slots[set.find(j)] {
+                    groups[slot].insert(tiles[i]);
+                }
+            }
+        }
+        groups.into_iter().collect()
+    }
+    ///Updates `groups` after the tiles at the `changed` handles were banished, moved or replaced.
+    ///
+    /// Only the groups touching a changed tile are split apart and merged back together,
+    /// so this is much cheaper than calling `owanimo_grouper` again after every link of a chain.
+    /// Groups that didn't change keep their `GroupId`.
+    ///
+    /// If a wildcard is in or next to the part being regrouped, or is already in more than one group,
+    /// everything is grouped from scratch instead, since a wildcard can be in more than one group.
+    fn owanimo_regroup<S: GroupSet<Self::Handle>>(
+        &self,
+        groups: &mut Groups<Self::Handle, S>,
+        changed: impl IntoIterator<Item = Self::Handle>,
+    ) {
+        if groups.overlapping() {
+            *groups = self.owanimo_grouper_in();
+            return;
+        }
+        let mut dirty = S::default();
+        let mut pending: Vec<Self::Handle> = vec![];
+        for handle in changed {
+            match groups.find(&handle) {
+                Some(group) => pending.extend(group.into_iter().filter(|&h| dirty.insert(h))),
+                None => {
+                    if dirty.insert(handle) {
+                        pending.push(handle);
+                    }
+                }
+            }
+        }
+        let mut seen = S::default();
+        for start in pending {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut group: S = [start].into_iter().collect();
+            let mut stack = vec![start];
+            while let Some(tile) = stack.pop() {
+                if self.wildcard(&tile) {
+                    *groups = self.owanimo_grouper_in();
+                    return;
+                }
+                for neighbor in self.neighbors(&tile) {
+                    //only the tiles touching the regrouped part are looked at, so this stays cheap
+                    if self.wildcard(&neighbor) {
+                        *groups = self.owanimo_grouper_in();
+                        return;
+                    }
+                    if seen.contains(&neighbor)
+                        || self.block(&tile)
+                        || self.block(&neighbor)
+                        || !self.connects(&tile, &neighbor)
+                    {
+                        continue;
+                    }
+                    if dirty.contains(&neighbor) {
+                        seen.insert(neighbor);
+                        group.insert(neighbor);
+                        stack.push(neighbor);
+                    } else if let Some(absorbed) = groups.find(&neighbor) {
+                        //an untouched group is already connected inside, but it may still touch other changed tiles
+                        for h in absorbed {
+                            seen.insert(h);
+                            group.insert(h);
+                            stack.push(h);
+                        }
+                    }
+                }
+            }
+            groups.push(group);
+        }
+    }
+    ///The original first part of the Owanimo spell, kept around to compare against `owanimo_grouper`.
+    ///
+    /// Gives the same groups, but merges them one tile at a time, so it gets slow on big boards.
+    /// It doesn't know about wildcards either, so they merge every group they touch.
+    fn owanimo_grouper_naive(&self) -> Groups<Self::Handle> {
         let mut groups = Groups::default();
         for tile in self.tiles() {
             let mut me_group: HashSet<Self::Handle> = [tile].into_iter().collect();
             for neighbor in self.neighbors(&tile) {
-                if self.connects(&tile, &neighbor) {
-                    if let Some(x) = groups.find(&neighbor) {
-                        me_group.extend(x);
-                    }
+                if self.connects(&tile, &neighbor)
+                    && let Some(x) = groups.find(&neighbor)
+                {
+                    me_group.extend(x);
                 }
             }
             groups.push(me_group);
@@ -58,131 +295,482 @@ pub trait Board {
     }
 }
 
+///A set of handles that can be used to store a group of beings.
+///
+/// `HashSet` works for any handle, `dense::BitSet` is much faster for handles that implement `dense::DenseHandle`.
+pub trait GroupSet<H: BoardHandle>:
+    Clone + Default + FromIterator<H> + Extend<H> + IntoIterator<Item = H>
+{
+    ///What `Groups` and `RefGroups` use to find which group a handle is in.
+    type Index: GroupIndex<H>;
+    fn contains(&self, handle: &H) -> bool;
+    ///Adds the handle, returning `true` if it wasn't already in the set.
+    fn insert(&mut self, handle: H) -> bool;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    ///Iterates through every handle in the set.
+    fn handles(&self) -> impl Iterator<Item = H>;
+}
+
+///A map from handles to the `GroupId` of the group they are in.
+pub trait GroupIndex<H: BoardHandle>: Clone + Default {
+    fn get(&self, handle: &H) -> Option<GroupId>;
+    fn insert(&mut self, handle: H, id: GroupId);
+    fn remove(&mut self, handle: &H);
+    fn contains(&self, handle: &H) -> bool {
+        self.get(handle).is_some()
+    }
+}
+
+impl<H: BoardHandle> GroupSet<H> for HashSet<H> {
+    type Index = HashMap<H, GroupId>;
+    fn contains(&self, handle: &H) -> bool {
+        HashSet::contains(self, handle)
+    }
+    fn insert(&mut self, handle: H) -> bool {
+        HashSet::insert(self, handle)
+    }
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+    fn handles(&self) -> impl Iterator<Item = H> {
+        self.iter().copied()
+    }
+}
+
+impl<H: BoardHandle> GroupIndex<H> for HashMap<H, GroupId> {
+    fn get(&self, handle: &H) -> Option<GroupId> {
+        HashMap::get(self, handle).copied()
+    }
+    fn insert(&mut self, handle: H, id: GroupId) {
+        HashMap::insert(self, handle, id);
+    }
+    fn remove(&mut self, handle: &H) {
+        HashMap::remove(self, handle);
+    }
+}
+
+///A stable name for one group inside a `Groups` or a `RefGroups`.
+///
+/// Removing other groups never changes the `GroupId` of a group, so it is safe to hold on to one
+/// instead of cloning the group itself.
+/// `Groups::as_ref`, `RefGroups::to_owned` and the popping steps of the spell all keep the `GroupId`s they were given,
+/// but a `RefGroups` collected from an iterator numbers its groups afresh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupId(pub usize);
+
 #[derive(Clone)]
-pub struct Groups<H: BoardHandle> {
-    pub groups: Vec<HashSet<H>>,
+pub struct Groups<H: BoardHandle, S: GroupSet<H> = HashSet<H>> {
+    groups: Vec<Option<S>>,
+    index: S::Index,
+    overlapping: bool,
 }
 
-impl<H: BoardHandle> Default for Groups<H> {
-    fn default() -> Groups<H> {
-        Groups { groups: vec![] }
+impl<H: BoardHandle, S: GroupSet<H>> Default for Groups<H, S> {
+    fn default() -> Groups<H, S> {
+        Groups {
+            groups: vec![],
+            index: Default::default(),
+            overlapping: false,
+        }
     }
 }
 
 #[derive(Clone)]
-pub struct RefGroups<'a, H: BoardHandle> {
-    pub groups: Vec<alloc::borrow::Cow<'a, HashSet<H>>>,
+pub struct RefGroups<'a, H: BoardHandle, S: GroupSet<H> = HashSet<H>> {
+    groups: Vec<Option<Cow<'a, S>>>,
+    index: S::Index,
+    blast: Option<GroupId>,
 }
 
-impl<'a, H: BoardHandle> Default for RefGroups<'a, H> {
+impl<'a, H: BoardHandle, S: GroupSet<H>> Default for RefGroups<'a, H, S> {
     fn default() -> Self {
-        RefGroups { groups: vec![] }
+        RefGroups {
+            groups: vec![],
+            index: Default::default(),
+            blast: None,
+        }
     }
 }
 
-impl<H: BoardHandle> Groups<H> {
+impl<H: BoardHandle, S: GroupSet<H>> Groups<H, S> {
     ///Find a group containing the handle provided, and extract it from the `Groups`.
     ///
     /// This is used in the first part of the Owanimo spell.
-    pub fn find(&mut self, handle: &H) -> Option<HashSet<H>> {
-        self.groups.extract_if(.., |g| g.contains(handle)).next()
+    pub fn find(&mut self, handle: &H) -> Option<S> {
+        let id = self.group_of(handle)?;
+        self.remove(id)
     }
-    ///Add a group into the `Groups`.
-    pub fn push(&mut self, group: HashSet<H>) {
-        self.groups.push(group);
+    ///Add a group into the `Groups`, returning the `GroupId` it can be found at.
+    pub fn push(&mut self, group: S) -> GroupId {
+        let id = GroupId(self.groups.len());
+        for h in group.handles() {
+            self.overlapping |= self.index.contains(&h);
+            self.index.insert(h, id);
+        }
+        self.groups.push(Some(group));
+        id
     }
-    ///Gets a `RefGroups` from this `Groups`
-    pub fn as_ref(&self) -> RefGroups<H> {
-        self.groups.iter().collect()
+    ///Take the group with this `GroupId` out of the `Groups`.
+    pub fn remove(&mut self, id: GroupId) -> Option<S> {
+        let group = self.groups.get_mut(id.0)?.take()?;
+        for handle in group.handles() {
+            if self.index.get(&handle) != Some(id) {
+                continue;
+            }
+            //a wildcard may still be in another group
+            let other = self
+                .overlapping
+                .then(|| self.ids().find(|(_, g)| g.contains(&handle)))
+                .flatten()
+                .map(|(other, _)| other);
+            match other {
+                Some(other) => self.index.insert(handle, other),
+                None => self.index.remove(&handle),
+            }
+        }
+        Some(group)
     }
-}
-
-impl<'a, H: BoardHandle> FromIterator<alloc::borrow::Cow<'a, HashSet<H>>> for RefGroups<'a, H> {
-    fn from_iter<T: IntoIterator<Item = alloc::borrow::Cow<'a, HashSet<H>>>>(iter: T) -> Self {
+    ///Has a handle ever been put in more than one group, because of wildcards?
+    pub fn overlapping(&self) -> bool {
+        self.overlapping
+    }
+    ///Which group is the handle in? Takes constant time.
+    pub fn group_of(&self, handle: &H) -> Option<GroupId> {
+        self.index.get(handle)
+    }
+    ///Is the handle in any group? Takes constant time.
+    pub fn contains(&self, handle: &H) -> bool {
+        self.index.contains(handle)
+    }
+    ///Gets the group with this `GroupId`, if it hasn't been removed.
+    pub fn get(&self, id: GroupId) -> Option<&S> {
+        self.groups.get(id.0)?.as_ref()
+    }
+    ///Iterates through every group still in the `Groups`, along with its `GroupId`.
+    pub fn ids(&self) -> impl Iterator<Item = (GroupId, &S)> {
+        self.groups
+            .iter()
+            .enumerate()
+            .filter_map(|(i, g)| Some((GroupId(i), g.as_ref()?)))
+    }
+    ///Iterates through every group still in the `Groups`.
+    pub fn iter(&self) -> impl Iterator<Item = &S> {
+        self.groups.iter().flatten()
+    }
+    ///How many groups are still in the `Groups`.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+    ///Every slot in the `Groups`, indexed by `GroupId`, removed groups are left as `None`.
+    pub fn as_slice(&self) -> &[Option<S>] {
+        &self.groups
+    }
+    ///Gets a `RefGroups` from this `Groups`, every group keeps its `GroupId`.
+    pub fn as_ref(&self) -> RefGroups<'_, H, S> {
         RefGroups {
-            groups: iter.into_iter().collect(),
+            groups: self
+                .groups
+                .iter()
+                .map(|g| g.as_ref().map(Cow::Borrowed))
+                .collect(),
+            index: self.index.clone(),
+            blast: None,
         }
     }
 }
 
-impl<'a, H: BoardHandle> FromIterator<&'a HashSet<H>> for RefGroups<'a, H> {
-    fn from_iter<T: IntoIterator<Item = &'a HashSet<H>>>(iter: T) -> Self {
+impl<'a, H: BoardHandle, S: GroupSet<H>> FromIterator<Cow<'a, S>> for RefGroups<'a, H, S> {
+    fn from_iter<T: IntoIterator<Item = Cow<'a, S>>>(iter: T) -> Self {
+        let groups: Vec<_> = iter.into_iter().map(Some).collect();
         RefGroups {
-            groups: iter.into_iter().map(alloc::borrow::Cow::Borrowed).collect(),
+            index: index_of(&groups),
+            groups,
+            blast: None,
         }
     }
 }
 
-impl<H: BoardHandle> FromIterator<HashSet<H>> for Groups<H> {
-    fn from_iter<T: IntoIterator<Item = HashSet<H>>>(iter: T) -> Self {
-        Groups {
-            groups: FromIterator::from_iter(iter),
+impl<'a, H: BoardHandle, S: GroupSet<H>> FromIterator<&'a S> for RefGroups<'a, H, S> {
+    fn from_iter<T: IntoIterator<Item = &'a S>>(iter: T) -> Self {
+        iter.into_iter().map(Cow::Borrowed).collect()
+    }
+}
+
+impl<H: BoardHandle, S: GroupSet<H>> FromIterator<S> for Groups<H, S> {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut groups = Groups::default();
+        for group in iter {
+            groups.push(group);
         }
+        groups
     }
 }
 
-impl<'a, 'b: 'a, H: BoardHandle> IntoIterator for &'b RefGroups<'a, H> {
-    type Item = alloc::borrow::Cow<'a, HashSet<H>>;
+impl<'a, 'b: 'a, H: BoardHandle, S: GroupSet<H>> IntoIterator for &'b RefGroups<'a, H, S> {
+    type Item = Cow<'a, S>;
     type IntoIter =
-        core::iter::Cloned<<&'b [alloc::borrow::Cow<'a, HashSet<H>>] as IntoIterator>::IntoIter>;
+        core::iter::Cloned<core::iter::Flatten<core::slice::Iter<'b, Option<Cow<'a, S>>>>>;
     fn into_iter(self) -> Self::IntoIter {
-        (&self.groups).into_iter().cloned()
+        self.groups.iter().flatten().cloned()
+    }
+}
+
+///Maps every handle to the slot of the group it's in.
+fn index_of<H: BoardHandle, S: GroupSet<H>>(groups: &[Option<Cow<'_, S>>]) -> S::Index {
+    let mut index = S::Index::default();
+    for (i, g) in groups.iter().enumerate() {
+        for h in g.iter().flat_map(|g| g.handles()) {
+            index.insert(h, GroupId(i));
+        }
     }
+    index
 }
 
-impl<'a, H: BoardHandle> RefGroups<'a, H> {
+impl<'a, H: BoardHandle, S: GroupSet<H>> RefGroups<'a, H, S> {
     ///Find a group containing the handle provided, and extract it from the `RefGroups`.
-    pub fn find(&self, handle: &H) -> Option<alloc::borrow::Cow<HashSet<H>>> {
-        self.groups
-            .iter()
-            .filter(|g| g.contains(handle))
-            .map(Clone::clone)
-            .next()
+    pub fn find(&self, handle: &H) -> Option<Cow<'_, S>> {
+        self.groups.get(self.group_of(handle)?.0)?.clone()
     }
     ///Find a group containing the handle provided, and return `true` if it exists
     ///
     /// This is used in the third part of the Owanimo spell.
     pub fn test(&self, handle: &H) -> bool {
+        self.index.contains(handle)
+    }
+    ///Which group is the handle in? Takes constant time.
+    pub fn group_of(&self, handle: &H) -> Option<GroupId> {
+        self.index.get(handle)
+    }
+    ///Gets the group with this `GroupId`, if it's still here.
+    pub fn get(&self, id: GroupId) -> Option<&S> {
+        self.groups.get(id.0)?.as_deref()
+    }
+    ///Iterates through every group, without cloning them.
+    pub fn iter(&self) -> impl Iterator<Item = &S> {
+        self.groups.iter().flatten().map(|g| g.as_ref())
+    }
+    ///Iterates through every group along with its `GroupId`, without cloning them.
+    pub fn ids(&self) -> impl Iterator<Item = (GroupId, &S)> {
         self.groups
             .iter()
-            .filter(|g| g.contains(handle))
-            .next()
-            .is_some()
+            .enumerate()
+            .filter_map(|(i, g)| Some((GroupId(i), g.as_deref()?)))
+    }
+    ///Every slot in the `RefGroups`, indexed by `GroupId`, groups that were left out are `None`.
+    pub fn as_slice(&self) -> &[Option<Cow<'a, S>>] {
+        &self.groups
+    }
+    ///The group of beings caught in bomb blasts by `owanimo_bombs`, if any were.
+    ///
+    /// They weren't banished as a group of their own, so colour and group bonuses should skip it.
+    pub fn blast(&self) -> Option<GroupId> {
+        self.blast
+    }
+    ///Iterates through every group except the `blast`, the ones colour and group bonuses look at.
+    pub fn bonus_groups(&self) -> impl Iterator<Item = &S> {
+        self.ids()
+            .filter(|(id, _)| Some(*id) != self.blast)
+            .map(|(_, g)| g)
+    }
+    ///Iterates through every tile in all of the groups, a wildcard in more than one group only comes up once.
+    pub fn tiles(&self) -> impl Iterator<Item = H> {
+        self.ids()
+            .flat_map(move |(id, g)| g.handles().filter(move |h| self.index.get(h) == Some(id)))
+    }
+    ///How many different tiles are in all of the groups, a wildcard in more than one group only counts once.
+    pub fn tile_count(&self) -> usize {
+        self.tiles().count()
+    }
+    ///How many groups there are.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+    ///Borrows the groups `keep` picks out, which keep their `GroupId`s.
+    fn keep(&self, mut keep: impl FnMut(&S) -> bool) -> RefGroups<'_, H, S> {
+        let groups: Vec<_> = self
+            .groups
+            .iter()
+            .map(|g| g.as_deref().filter(|g| keep(g)).map(Cow::Borrowed))
+            .collect();
+        RefGroups {
+            index: index_of(&groups),
+            groups,
+            blast: self.blast,
+        }
+    }
+    ///Borrows every group, and adds `extra` groups after them.
+    pub(crate) fn with_extra(&self, extra: impl IntoIterator<Item = S>) -> RefGroups<'_, H, S> {
+        let mut index = self.index.clone();
+        let mut groups: Vec<_> = self
+            .groups
+            .iter()
+            .map(|g| g.as_deref().map(Cow::Borrowed))
+            .collect();
+        for group in extra {
+            for h in group.handles() {
+                index.insert(h, GroupId(groups.len()));
+            }
+            groups.push(Some(Cow::Owned(group)));
+        }
+        RefGroups {
+            groups,
+            index,
+            blast: self.blast,
+        }
     }
     ///Second part of the Owanimo spell, selects large enough groups of beings to banish to the otherworld
     ///
     /// Most mages call this step "popping", because the beings most used for this ritual POP when they are banished.
     ///
     /// For the optional third part of the spell, see `owanimo_nuisance`
-    pub fn owanimo_pop(&self, pieces_to_pop: usize) -> RefGroups<H> {
-        self.into_iter()
-            .filter(|g| g.len() >= pieces_to_pop)
-            .collect()
+    pub fn owanimo_pop(&self, pieces_to_pop: usize) -> RefGroups<'_, H, S> {
+        self.keep(|g| g.len() >= pieces_to_pop)
     }
-    ///Turns this `RefGroups` into a `Groups`
-    pub fn to_owned(&self) -> Groups<H> {
-        self.into_iter().map(|x| x.into_owned()).collect()
+    ///Like `owanimo_pop`, but lets a `PopRule` decide which groups are banished.
+    ///
+    /// Groups with a block in them never pop, whatever the rule says.
+    pub fn owanimo_pop_by<B: Board<Handle = H>>(
+        &self,
+        board: &B,
+        rule: &impl PopRule<B, S>,
+    ) -> RefGroups<'_, H, S> {
+        self.keep(|g| !g.handles().any(|h| board.block(&h)) && rule.pops(board, g))
+    }
+    ///Turns this `RefGroups` into a `Groups`, every group keeps its `GroupId`.
+    pub fn to_owned(&self) -> Groups<H, S> {
+        Groups {
+            groups: self.groups.iter().map(|g| g.as_deref().cloned()).collect(),
+            index: self.index.clone(),
+            overlapping: self.iter().map(|g| g.len()).sum::<usize>() != self.tile_count(),
+        }
     }
 }
 
 ///Calculates a Score from the state of the board and the pieces that have popped.
-pub trait Scorer<B: Board> {
-    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle>) -> u64;
+pub trait Scorer<B: Board, S: GroupSet<B::Handle> = HashSet<<B as Board>::Handle>> {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64;
 }
 
-impl<B: Board> Scorer<B> for () {
-    fn score(&self, _board: &B, _popped: &RefGroups<<B as Board>::Handle>) -> u64 {
+impl<B: Board, S: GroupSet<B::Handle>> Scorer<B, S> for () {
+    fn score(&self, _board: &B, _popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
         0
     }
 }
 
-impl<B: Board> Scorer<B> for u64 {
-    fn score(&self, _board: &B, _popped: &RefGroups<<B as Board>::Handle>) -> u64 {
+impl<B: Board, S: GroupSet<B::Handle>> Scorer<B, S> for u64 {
+    fn score(&self, _board: &B, _popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
         *self
     }
 }
 
+///Decides whether a group of beings is ready to be banished, for `RefGroups::owanimo_pop_by`.
+///
+/// A plain `usize` pops groups of at least that many beings, see `pop` for the other rules and for combining them.
+pub trait PopRule<B: Board, S: GroupSet<B::Handle> = HashSet<<B as Board>::Handle>> {
+    fn pops(&self, board: &B, group: &S) -> bool;
+}
+
+impl<B: Board, S: GroupSet<B::Handle>> PopRule<B, S> for usize {
+    fn pops(&self, _board: &B, group: &S) -> bool {
+        group.len() >= *self
+    }
+}
+
+///Something went wrong reading a board drawn as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownSymbol { at: (usize, usize), symbol: char },
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::UnknownSymbol { at, symbol } => {
+                write!(f, "Unknown Symbol {} at {},{}", symbol, at.0, at.1)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+///Reads a board drawn as text, calling `tile` with the position and symbol of every tile.
+///
+/// Rows are drawn from top to bottom, so the last non-empty line is `y = 0`.
+/// Only the first `width` symbols of the first `height` non-empty lines are read.
+pub fn parse_rows(
+    s: &str,
+    width: usize,
+    height: usize,
+    mut tile: impl FnMut((usize, usize), char) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    let lines = s
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .take(height)
+        .collect::<Vec<_>>();
+    let start_line = lines.len().saturating_sub(1);
+    for (y_bottom, line) in lines.into_iter().enumerate() {
+        let y = start_line - y_bottom;
+        for (x, chr) in line.trim().chars().take(width).enumerate() {
+            tile((x, y), chr)?;
+        }
+    }
+    Ok(())
+}
+
 pub trait BanishBoard: Board {
     ///Banish or Pop the being at `handle`
     fn banish(&mut self, handle: Self::Handle);
+    ///The other beings banished along with the being at `handle`, if it's a bomb.
+    ///
+    /// Should leave out air and the bomb itself, see `RefGroups::owanimo_bombs`.
+    fn blast(&self, _handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        core::iter::empty()
+    }
+}
+
+///A board that beings can be summoned onto, like the pieces a player drops.
+pub trait PlaceBoard: Board {
+    type Being;
+    ///Put `being` at `handle`, replacing whatever was there.
+    fn place(&mut self, handle: Self::Handle, being: Self::Being);
+    ///The tile `by` away from `handle`, as `(right, up)`, or `None` if that's off the board.
+    fn step(&self, handle: &Self::Handle, by: (isize, isize)) -> Option<Self::Handle>;
+}
+
+impl<'a, H: BoardHandle, S: GroupSet<H>> RefGroups<'a, H, S> {
+    ///Sets off every bomb about to be banished, adding every being caught in a blast to one more group, the `blast`.
+    ///
+    /// Bombs caught in a blast go off too, but blocks are never caught.
+    /// Use this after `owanimo_nuisance`, so that bombs in the nuisance go off as well.
+    pub fn owanimo_bombs<B: BanishBoard + Board<Handle = H>>(
+        &self,
+        board: &B,
+    ) -> RefGroups<'_, H, S> {
+        let mut caught = S::default();
+        let mut pending: Vec<H> = self.tiles().collect();
+        while let Some(bomb) = pending.pop() {
+            for h in board.blast(&bomb) {
+                if !board.block(&h) && !self.test(&h) && caught.insert(h) {
+                    pending.push(h);
+                }
+            }
+        }
+        if caught.is_empty() {
+            return self.with_extra([]);
+        }
+        let mut blasted = self.with_extra([caught]);
+        blasted.blast = Some(GroupId(blasted.groups.len() - 1));
+        blasted
+    }
 }
@@ -0,0 +1,93 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use hashbrown::HashSet;
+
+use crate::{
+    GroupSet, RefGroups, grid::CartesianBoard, standard::ColorBoard, unionfind::DisjointSet,
+};
+
+///Finds straight lines of beings instead of connected groups, for games where only a row of three or more counts.
+///
+/// Every grid board with colours gets this for free.
+pub trait LineBoard: CartesianBoard + ColorBoard {
+    ///Another way to do the first two parts of the Owanimo spell at once,
+    /// finds every horizontal and vertical run of at least `run` beings of the same colour.
+    ///
+    /// Runs that cross each other, like in an L or a T, become one group.
+    /// The result works with `owanimo_nuisance`, any `Scorer` and `banish` just like `owanimo_pop` does.
+    fn owanimo_lines(&self, run: usize) -> RefGroups<'static, (usize, usize)> {
+        self.owanimo_lines_in::<HashSet<_>>(run)
+    }
+    ///Like `owanimo_lines`, but stores the groups in any `GroupSet`.
+    fn owanimo_lines_in<S: GroupSet<(usize, usize)>>(
+        &self,
+        run: usize,
+    ) -> RefGroups<'static, (usize, usize), S> {
+        let (width, height) = (self.width(), self.height());
+        let mut lines = Lines {
+            sets: DisjointSet::new(width * height),
+            matched: vec![false; width * height],
+            height,
+        };
+        for y in 0..height {
+            lines.mark_runs(self, (0..width).map(|x| (x, y)), run);
+        }
+        for x in 0..width {
+            lines.mark_runs(self, (0..height).map(|y| (x, y)), run);
+        }
+        let mut groups: Vec<Option<S>> = vec![None; width * height];
+        for x in 0..width {
+            for y in 0..height {
+                let i = lines.index((x, y));
+                if lines.matched[i] {
+                    let root = lines.sets.find(i);
+                    groups[root].get_or_insert_default().insert((x, y));
+                }
+            }
+        }
+        groups.into_iter().flatten().map(Cow::Owned).collect()
+    }
+}
+
+impl<T: CartesianBoard + ColorBoard> LineBoard for T {}
+
+///The working state of `owanimo_lines`, runs are joined together in `sets`.
+struct Lines {
+    sets: DisjointSet,
+    matched: Vec<bool>,
+    height: usize,
+}
+
+impl Lines {
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        x * self.height + y
+    }
+    ///Joins up and marks every run of at least `run` beings of the same colour along `line`.
+    fn mark_runs<B: ColorBoard + CartesianBoard + ?Sized>(
+        &mut self,
+        board: &B,
+        line: impl Iterator<Item = (usize, usize)>,
+        run: usize,
+    ) {
+        let mut current: Vec<(usize, usize)> = Vec::new();
+        let mut color = None;
+        //a trailing `None` flushes the last run
+        for handle in line.map(Some).chain([None]) {
+            let next = handle.and_then(|h| board.color(&h));
+            if next.is_none() || next != color {
+                if color.is_some() && current.len() >= run {
+                    let first = self.index(current[0]);
+                    for &h in &current {
+                        let i = self.index(h);
+                        self.sets.union(first, i);
+                        self.matched[i] = true;
+                    }
+                }
+                current.clear();
+            }
+            color = next;
+            current.extend(handle);
+        }
+    }
+}
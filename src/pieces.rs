@@ -0,0 +1,137 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::rng::Rng;
+
+///Which kind of piece comes next, see `Piece`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PieceShape {
+    Pair,
+    Triple,
+    Quad,
+    Big,
+}
+
+///A piece for the player to drop, made of beings of one or two colors.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Piece<C> {
+    ///A pivot and a satellite, the satellite starting above the pivot.
+    Pair { pivot: C, satellite: C },
+    ///Two beings of one color and one of another, in an L.
+    Triple { pair: C, odd: C },
+    ///Two beings of each color, in a square.
+    Quad { a: C, b: C },
+    ///Four beings of one color, in a square.
+    Big(C),
+}
+
+impl<C> Piece<C> {
+    pub fn shape(&self) -> PieceShape {
+        match self {
+            Piece::Pair { .. } => PieceShape::Pair,
+            Piece::Triple { .. } => PieceShape::Triple,
+            Piece::Quad { .. } => PieceShape::Quad,
+            Piece::Big(_) => PieceShape::Big,
+        }
+    }
+}
+
+///Deals out pieces from a seed, the same way every time on every platform.
+///
+/// Pieces are made lazily, so the public fields can be changed freely before the first piece is looked at.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PieceGenerator<C, R> {
+    pub rng: R,
+    ///The colors in play.
+    pub colors: Vec<C>,
+    ///How many of the first pieces are restricted to fewer colors, 2 in Puyo Puyo Tsu.
+    pub first_pieces: usize,
+    ///How many colors the first pieces may use, 3 in Puyo Puyo Tsu.
+    pub first_colors: usize,
+    ///The shapes to deal, over and over, like a Puyo Puyo Fever character's drop pattern.
+    pub shapes: Vec<PieceShape>,
+    dealt: usize,
+    queue: VecDeque<Piece<C>>,
+}
+
+impl<C: Copy, R: Rng> PieceGenerator<C, R> {
+    ///Picks `color_count` colors out of `palette` at random, and deals pairs of them.
+    pub fn new(palette: &[C], color_count: usize, mut rng: R) -> Self {
+        let mut colors = palette.to_vec();
+        let color_count = color_count.min(colors.len());
+        for i in 0..color_count {
+            let j = i + rng.below((colors.len() - i) as u64) as usize;
+            colors.swap(i, j);
+        }
+        colors.truncate(color_count);
+        PieceGenerator {
+            rng,
+            colors,
+            first_pieces: 2,
+            first_colors: 3,
+            shapes: alloc::vec![PieceShape::Pair],
+            dealt: 0,
+            queue: VecDeque::new(),
+        }
+    }
+    ///How many pieces have been made so far, including the ones waiting in the queue.
+    pub fn dealt(&self) -> usize {
+        self.dealt
+    }
+    ///Looks at an upcoming piece without taking it, `peek(0)` is NEXT and `peek(1)` is NEXT-NEXT.
+    pub fn peek(&mut self, n: usize) -> Option<&Piece<C>> {
+        while self.queue.len() <= n {
+            let piece = self.deal()?;
+            self.queue.push_back(piece);
+        }
+        self.queue.get(n)
+    }
+    fn color(&mut self) -> Option<C> {
+        let usable = if self.dealt < self.first_pieces {
+            self.first_colors.min(self.colors.len())
+        } else {
+            self.colors.len()
+        };
+        if usable == 0 {
+            return None;
+        }
+        let index = self.rng.below(usable as u64) as usize;
+        Some(self.colors[index])
+    }
+    fn deal(&mut self) -> Option<Piece<C>> {
+        let shape = if self.shapes.is_empty() {
+            PieceShape::Pair
+        } else {
+            self.shapes[self.dealt % self.shapes.len()]
+        };
+        let piece = match shape {
+            PieceShape::Pair => Piece::Pair {
+                pivot: self.color()?,
+                satellite: self.color()?,
+            },
+            PieceShape::Triple => Piece::Triple {
+                pair: self.color()?,
+                odd: self.color()?,
+            },
+            PieceShape::Quad => Piece::Quad {
+                a: self.color()?,
+                b: self.color()?,
+            },
+            PieceShape::Big => Piece::Big(self.color()?),
+        };
+        self.dealt += 1;
+        Some(piece)
+    }
+}
+
+impl<C: Copy, R: Rng> Iterator for PieceGenerator<C, R> {
+    type Item = Piece<C>;
+
+    ///Takes the piece at the front of the queue, which becomes the one in play.
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.queue.pop_front() {
+            Some(piece) => Some(piece),
+            None => self.deal(),
+        }
+    }
+}
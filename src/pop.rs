@@ -0,0 +1,77 @@
+use crate::{Board, GroupSet, PopRule};
+
+///Pops the groups that both `A` and `B` pop.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<Bd: Board, S: GroupSet<Bd::Handle>, A: PopRule<Bd, S>, B: PopRule<Bd, S>> PopRule<Bd, S>
+    for And<A, B>
+{
+    fn pops(&self, board: &Bd, group: &S) -> bool {
+        self.0.pops(board, group) && self.1.pops(board, group)
+    }
+}
+
+///Pops the groups that either `A` or `B` pop.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<Bd: Board, S: GroupSet<Bd::Handle>, A: PopRule<Bd, S>, B: PopRule<Bd, S>> PopRule<Bd, S>
+    for Or<A, B>
+{
+    fn pops(&self, board: &Bd, group: &S) -> bool {
+        self.0.pops(board, group) || self.1.pops(board, group)
+    }
+}
+
+///Pops the groups that `A` doesn't.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Not<A>(pub A);
+
+impl<Bd: Board, S: GroupSet<Bd::Handle>, A: PopRule<Bd, S>> PopRule<Bd, S> for Not<A> {
+    fn pops(&self, board: &Bd, group: &S) -> bool {
+        !self.0.pops(board, group)
+    }
+}
+
+///A different group size for each colour, like beings that need 3 friends to pop while others need 5.
+///
+///Note: Only checks the first color of every group, skipping over wildcards. Groups without a colour never pop.
+#[cfg(feature = "standard")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ColorThresholds<'a, C> {
+    pub table: &'a [(C, usize)],
+    ///The size for colours missing from `table`.
+    pub otherwise: usize,
+}
+
+#[cfg(feature = "standard")]
+impl<'a, B: crate::standard::ColorBoard, S: GroupSet<B::Handle>> PopRule<B, S>
+    for ColorThresholds<'a, B::Color>
+{
+    fn pops(&self, board: &B, group: &S) -> bool {
+        let Some(color) = group.handles().find_map(|h| board.color(&h)) else {
+            return false;
+        };
+        let needed = self
+            .table
+            .iter()
+            .find(|(c, _)| *c == color)
+            .map_or(self.otherwise, |&(_, needed)| needed);
+        group.len() >= needed
+    }
+}
+
+///Only pops groups with a being in row `y`, counting up from the bottom.
+///
+/// Air tiles are groups too, so this is meant to be combined with a size using `And`.
+#[cfg(feature = "grid")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TouchesRow(pub usize);
+
+#[cfg(feature = "grid")]
+impl<B: crate::grid::CartesianBoard, S: GroupSet<B::Handle>> PopRule<B, S> for TouchesRow {
+    fn pops(&self, _board: &B, group: &S) -> bool {
+        group.handles().any(|(_, y)| y == self.0)
+    }
+}
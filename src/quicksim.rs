@@ -1,26 +1,57 @@
+use alloc::vec::Vec;
+use hashbrown::HashSet;
+
 use crate::{
-    BanishBoard, Board, Scorer,
+    BanishBoard, Board, GroupSet, PopRule, Scorer,
     gravity::GravityBoard,
     standard::{NuisanceBoard, StandardScorer},
 };
 
-pub trait QuickSimBoard: Board + BanishBoard + GravityBoard + NuisanceBoard + Sized {
+///Simulates whole chains, storing groups in `S`.
+///
+/// Boards with `dense::DenseHandle`s can implement `QuickSimBoard<dense::BitSet<Handle>>` instead to skip hashing.
+pub trait QuickSimBoard<S: GroupSet<<Self as Board>::Handle> = HashSet<<Self as Board>::Handle>>:
+    Board + BanishBoard + GravityBoard + NuisanceBoard + Sized
+{
     ///Output: ()
+    ///
+    /// Pass `&4` as the `pop_rule` for the usual groups of 4, or any other `PopRule`.
     fn quick_sim(
         &mut self,
-        pieces_to_pop: usize,
-        pc: &impl Scorer<Self>,
-        pb: &impl Scorer<Self>,
+        pop_rule: &impl PopRule<Self, S>,
+        pc: &impl Scorer<Self, S>,
+        pb: &impl Scorer<Self, S>,
+        chain_power_table: &[u64],
+        cb: &impl Scorer<Self, S>,
+        gb: &impl Scorer<Self, S>,
+    ) -> SimResult {
+        self.quick_sim_with_garbage(pop_rule, pc, pb, chain_power_table, cb, gb, &())
+    }
+    ///Like `quick_sim`, but `garbage` works out the nuisance each link sends on top of its score,
+    /// which is added up in `SimResult::bonus_garbage`.
+    ///
+    /// Pass `&standard::SunBonus { per_sun: .. }` here for Sun pieces.
+    #[allow(clippy::too_many_arguments)]
+    fn quick_sim_with_garbage(
+        &mut self,
+        pop_rule: &impl PopRule<Self, S>,
+        pc: &impl Scorer<Self, S>,
+        pb: &impl Scorer<Self, S>,
         chain_power_table: &[u64],
-        cb: &impl Scorer<Self>,
-        gb: &impl Scorer<Self>,
+        cb: &impl Scorer<Self, S>,
+        gb: &impl Scorer<Self, S>,
+        garbage: &impl Scorer<Self, S>,
     ) -> SimResult {
         let mut score = 0;
         let mut chain = 0;
         let mut pieces_cleared = 0;
         let mut max_pieces_at_once = 0;
+        let mut link_scores = Vec::new();
+        let mut bonus_garbage = 0;
+        let mut changed = S::default();
+        self.fall();
+        let mut grps = self.owanimo_grouper_in::<S>();
         loop {
-            self.fall();
             let scorer = StandardScorer {
                 chain_power: chain_power_table
                     .get(chain as usize)
@@ -32,30 +63,44 @@ pub trait QuickSimBoard: Board + BanishBoard + GravityBoard + NuisanceBoard + Si
                 pieces_cleared: pc,
                 phantom: Default::default(),
             };
-            let grps = self.owanimo_grouper();
             let binding = grps.as_ref();
-            let binding = binding.owanimo_pop(pieces_to_pop);
+            let binding = binding.owanimo_pop_by(self, pop_rule);
             let pg = binding.owanimo_nuisance(self);
+            let pg = pg.owanimo_bombs(self);
+            //the damage lives on the board, so it follows hard nuisance as it falls between links
+            let hits = binding.owanimo_hits(self);
             let this_score = scorer.score(self, &pg);
             let this_pieces_cleared = pc.score(self, &pg);
+            bonus_garbage += garbage.score(self, &pg);
             for g in &pg {
-                for &p in g.iter() {
+                for p in g.handles() {
                     self.banish(p);
+                    changed.insert(p);
                 }
             }
+            for (p, times) in hits {
+                self.damage(&p, times);
+                changed.insert(p);
+            }
             score += this_score;
             pieces_cleared += this_pieces_cleared;
             max_pieces_at_once = max_pieces_at_once.max(this_pieces_cleared);
             if this_pieces_cleared == 0 {
                 break;
             }
+            link_scores.push(this_score);
             chain += 1;
+            self.fall_tracked(&mut changed);
+            self.owanimo_regroup(&mut grps, core::mem::take(&mut changed));
         }
         SimResult {
             score,
             chain,
             pieces_cleared,
             max_pieces_at_once,
+            link_scores,
+            all_clear: chain > 0 && self.all_clear(),
+            bonus_garbage,
         }
     }
 }
@@ -65,4 +110,75 @@ pub struct SimResult {
     pub chain: u64,
     pub pieces_cleared: u64,
     pub max_pieces_at_once: u64,
+    ///The score of every link of the chain, in order, for working out the nuisance sent with `garbage::GarbageCalculator`.
+    pub link_scores: Vec<u64>,
+    ///Did the chain banish every being on the board?
+    pub all_clear: bool,
+    ///Extra nuisance to send on top of the score, from the `garbage` of `quick_sim_with_garbage` or an `AllClearBonus`.
+    pub bonus_garbage: u64,
+}
+
+///What an All Clear is worth.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AllClearReward {
+    ///Points, added to the score of a link so that they turn into nuisance along with it.
+    Score(u64),
+    ///Nuisance, sent on top of the score.
+    Garbage(u64),
+}
+
+///Hands out the bonus for an All Clear, either straight away or with the next chain like in Puyo Puyo Tsu.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AllClearBonus {
+    pub reward: AllClearReward,
+    ///Wait for the next chain before handing out the bonus?
+    pub next_chain: bool,
+    ///Is there a bonus waiting for the next chain?
+    pub pending: bool,
+}
+
+impl AllClearBonus {
+    pub fn new(reward: AllClearReward, next_chain: bool) -> Self {
+        AllClearBonus {
+            reward,
+            next_chain,
+            pending: false,
+        }
+    }
+    ///The Puyo Puyo Tsu bonus, 30 nuisance sent with the next chain.
+    pub fn tsu() -> Self {
+        AllClearBonus::new(AllClearReward::Garbage(30), true)
+    }
+    ///Adds the bonus to `result` if it has one coming, returning whether it did.
+    ///
+    /// Call this with every `SimResult`, in order, including the ones without a chain.
+    pub fn apply(&mut self, result: &mut SimResult) -> bool {
+        let earned = if self.next_chain {
+            let earned = self.pending && result.chain > 0;
+            if result.chain > 0 {
+                self.pending = result.all_clear;
+            }
+            earned
+        } else {
+            result.all_clear
+        };
+        if earned {
+            match self.reward {
+                AllClearReward::Score(points) => {
+                    result.score += points;
+                    //the bonus goes out with the first link, or with the link that cleared the board
+                    let link = if self.next_chain {
+                        result.link_scores.first_mut()
+                    } else {
+                        result.link_scores.last_mut()
+                    };
+                    if let Some(link) = link {
+                        *link += points;
+                    }
+                }
+                AllClearReward::Garbage(nuisance) => result.bonus_garbage += nuisance,
+            }
+        }
+        earned
+    }
 }
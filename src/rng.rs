@@ -0,0 +1,33 @@
+///A source of random numbers for anything that needs to be repeatable, like nuisance drops and piece queues.
+///
+/// Implement this for your own generator, or use `SplitMix64`.
+/// The same seed must always give the same numbers, so replays and netplay stay in lockstep.
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+    ///A number in `0..n`, or 0 if `n` is 0.
+    fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+}
+
+///A tiny, fast generator with a single `u64` of state, good enough for games.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SplitMix64 {
+    pub state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
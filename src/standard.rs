@@ -1,7 +1,7 @@
-use alloc::borrow::Cow;
+use alloc::vec::Vec;
 use hashbrown::HashSet;
 
-use crate::{Board, BoardHandle, RefGroups, Scorer};
+use crate::{Board, BoardHandle, GroupId, GroupSet, RefGroups, Scorer};
 
 /// Display the score as AxB, multiply the numbers together to get the actual score.
 ///
@@ -10,14 +10,14 @@ use crate::{Board, BoardHandle, RefGroups, Scorer};
 /// Feel free to make your own version of this function using the provided parts.
 ///
 /// Also, feel free to use `&()` or `&0` (or `&my_u64_score`) as scorers!
-pub fn score<B: Board>(
+pub fn score<B: Board, S: GroupSet<B::Handle>>(
     board: &B,
-    popped: &RefGroups<B::Handle>,
-    pieces_cleared: &impl Scorer<B>,
-    point_bonus: &impl Scorer<B>,
-    chain_power: &impl Scorer<B>,
-    color_bonus: &impl Scorer<B>,
-    group_bonus: &impl Scorer<B>,
+    popped: &RefGroups<B::Handle, S>,
+    pieces_cleared: &impl Scorer<B, S>,
+    point_bonus: &impl Scorer<B, S>,
+    chain_power: &impl Scorer<B, S>,
+    color_bonus: &impl Scorer<B, S>,
+    group_bonus: &impl Scorer<B, S>,
 ) -> (u64, u64) {
     let score = 10 * pieces_cleared.score(board, popped) + point_bonus.score(board, popped);
     let multiplier = chain_power.score(board, popped)
@@ -26,15 +26,7 @@ pub fn score<B: Board>(
     (score, multiplier)
 }
 
-pub struct StandardScorer<
-    'a,
-    B: Board,
-    PC: Scorer<B>,
-    PB: Scorer<B>,
-    CP: Scorer<B>,
-    CB: Scorer<B>,
-    GB: Scorer<B>,
-> {
+pub struct StandardScorer<'a, B: Board, PC, PB, CP, CB, GB> {
     pub pieces_cleared: &'a PC,
     pub point_bonus: &'a PB,
     pub chain_power: &'a CP,
@@ -43,10 +35,18 @@ pub struct StandardScorer<
     pub phantom: core::marker::PhantomData<B>,
 }
 
-impl<'a, B: Board, PC: Scorer<B>, PB: Scorer<B>, CP: Scorer<B>, CB: Scorer<B>, GB: Scorer<B>>
-    Scorer<B> for StandardScorer<'a, B, PC, PB, CP, CB, GB>
+impl<
+    'a,
+    B: Board,
+    S: GroupSet<B::Handle>,
+    PC: Scorer<B, S>,
+    PB: Scorer<B, S>,
+    CP: Scorer<B, S>,
+    CB: Scorer<B, S>,
+    GB: Scorer<B, S>,
+> Scorer<B, S> for StandardScorer<'a, B, PC, PB, CP, CB, GB>
 {
-    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle>) -> u64 {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
         let (a, b) = score(
             board,
             popped,
@@ -60,30 +60,45 @@ impl<'a, B: Board, PC: Scorer<B>, PB: Scorer<B>, CP: Scorer<B>, CB: Scorer<B>, G
     }
 }
 
-///Note that if you use Sun or Point pieces, you should implement your own Scorer here
+///Counts every popped being, wildcards popped in more than one group are only counted once.
+///
+/// If you use Sun or Point pieces, you probably want `ColoredPiecesCleared` instead.
 pub struct TrivialPiecesCleared;
 
-impl<B: Board> Scorer<B> for TrivialPiecesCleared {
-    fn score(&self, _board: &B, popped: &RefGroups<<B as Board>::Handle>) -> u64 {
-        popped.groups.iter().map(|x| x.len() as u64).sum()
+impl<B: Board, S: GroupSet<B::Handle>> Scorer<B, S> for TrivialPiecesCleared {
+    fn score(&self, _board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
+        popped.tile_count() as u64
+    }
+}
+
+///Only counts beings with a colour (and wildcards), so popped nuisance, Point and Sun pieces don't add to the score.
+pub struct ColoredPiecesCleared;
+
+impl<B: ColorBoard, S: GroupSet<B::Handle>> Scorer<B, S> for ColoredPiecesCleared {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
+        popped
+            .tiles()
+            .filter(|h| board.color(h).is_some() || board.wildcard(h))
+            .count() as u64
     }
 }
 
 pub trait ColorBoard: Board {
     type Color: BoardHandle;
+    ///Wildcards should have no colour, so that scorers can see the colour of the group they joined.
     fn color(&self, handle: &Self::Handle) -> Option<Self::Color>;
 }
 
-///Note: Only checks the first color of every group
+///Note: Only checks the first color of every group, skipping over wildcards and the `blast`
 pub struct ColorBonusTable<'a> {
     pub table: &'a [u64],
 }
 
-impl<'a, B: ColorBoard> Scorer<B> for ColorBonusTable<'a> {
-    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle>) -> u64 {
+impl<'a, B: ColorBoard, S: GroupSet<B::Handle>> Scorer<B, S> for ColorBonusTable<'a> {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
         let mut colors: HashSet<B::Color> = Default::default();
-        for g in &popped.groups {
-            if let Some(color) = g.iter().next().and_then(|handle| board.color(handle)) {
+        for g in popped.bonus_groups() {
+            if let Some(color) = g.handles().find_map(|handle| board.color(&handle)) {
                 colors.get_or_insert(color);
             };
         }
@@ -96,14 +111,14 @@ impl<'a, B: ColorBoard> Scorer<B> for ColorBonusTable<'a> {
 }
 
 pub trait GroupBoard: Board {
-    fn consider_for_group_bonus(&self, group: &HashSet<Self::Handle>) -> bool;
+    fn consider_for_group_bonus(&self, group: &impl GroupSet<Self::Handle>) -> bool;
 }
 
 pub trait GroupFromColorBoard: ColorBoard {}
 
 impl<T: GroupFromColorBoard> GroupBoard for T {
-    fn consider_for_group_bonus(&self, group: &HashSet<Self::Handle>) -> bool {
-        group.iter().next().and_then(|h| self.color(h)).is_some()
+    fn consider_for_group_bonus(&self, group: &impl GroupSet<Self::Handle>) -> bool {
+        group.handles().any(|h| self.color(&h).is_some())
     }
 }
 
@@ -111,10 +126,10 @@ pub struct GroupBonusTable<'a> {
     pub table: &'a [u64],
 }
 
-impl<'a, B: GroupBoard> Scorer<B> for GroupBonusTable<'a> {
-    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle>) -> u64 {
+impl<'a, B: GroupBoard, S: GroupSet<B::Handle>> Scorer<B, S> for GroupBonusTable<'a> {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
         let mut group_bonus = 0u64;
-        for g in &popped.groups {
+        for g in popped.bonus_groups() {
             if board.consider_for_group_bonus(g) {
                 group_bonus += self.table.get(g.len()).or(self.table.last()).unwrap_or(&0);
             }
@@ -123,30 +138,91 @@ impl<'a, B: GroupBoard> Scorer<B> for GroupBonusTable<'a> {
     }
 }
 
+///A board with Point pieces, which are worth extra points when they're banished.
+pub trait PointBoard: Board {
+    ///How many points the being at `handle` is worth, usually 0.
+    fn points(&self, handle: &Self::Handle) -> u64;
+}
+
+///Adds up the points of every popped being, for the `point_bonus` slot of `score`.
+pub struct PointBonus;
+
+impl<B: PointBoard, S: GroupSet<B::Handle>> Scorer<B, S> for PointBonus {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
+        popped.tiles().map(|h| board.points(&h)).sum()
+    }
+}
+
+///A board with Sun pieces, which send extra nuisance to the opponent when they're banished.
+pub trait SunBoard: Board {
+    fn sun(&self, handle: &Self::Handle) -> bool;
+}
+
+///The extra nuisance sent by popped Sun pieces, `per_sun` for each of them.
+///
+/// This isn't part of the score, pass it as the `garbage` of `QuickSimBoard::quick_sim_with_garbage`
+/// so that it ends up in `SimResult::bonus_garbage` instead, like `&SunBonus { per_sun: 3 }`.
+pub struct SunBonus {
+    pub per_sun: u64,
+}
+
+impl<B: SunBoard, S: GroupSet<B::Handle>> Scorer<B, S> for SunBonus {
+    fn score(&self, board: &B, popped: &RefGroups<<B as Board>::Handle, S>) -> u64 {
+        popped.tiles().filter(|h| board.sun(h)).count() as u64 * self.per_sun
+    }
+}
+
 pub trait NuisanceBoard: Board {
     ///Will this being be sucked into the otherworld if a neighboring being was banished?
     ///
     /// Usually, the beings weak to this behaviour look transparent. and may also be called garbage or ojama.
     fn nuisance(&self, handle: &Self::Handle) -> bool;
+    ///How many more hits the hard nuisance at `handle` can take before it turns into regular nuisance.
+    ///
+    /// Regular nuisance has none, and is banished by the first hit like always.
+    fn hit_points(&self, _handle: &Self::Handle) -> u32 {
+        0
+    }
+    ///Hits the hard nuisance at `handle` `hits` times.
+    ///
+    /// Boards with hard nuisance should take that many hit points away,
+    /// and turn it into regular nuisance once it has none left. Any extra hits are wasted.
+    fn damage(&mut self, _handle: &Self::Handle, _hits: u32) {}
 }
 
-impl<'a, H: BoardHandle> RefGroups<'a, H> {
+impl<'a, H: BoardHandle, S: GroupSet<H>> RefGroups<'a, H, S> {
     ///The Third part of the Owanimo spell, a Side Effect if you will, but an Important one.
     ///
     ///Use this function once you've opened portals to the otherworld with `owanimo_pop`
     pub fn owanimo_nuisance<B: NuisanceBoard + Board<Handle = H>>(
         &self,
         board: &B,
-    ) -> RefGroups<H> {
-        self.into_iter()
-            .chain(
-                board
-                    .tiles()
-                    .filter(|p| board.nuisance(p))
-                    .filter(|p| board.neighbors(p).any(|n| self.test(&n)))
-                    .map(|p| [p].into_iter().collect())
-                    .map(Cow::Owned),
-            )
+    ) -> RefGroups<'_, H, S> {
+        self.with_extra(
+            board
+                .tiles()
+                .filter(|p| board.nuisance(p) && !board.block(p) && board.hit_points(p) == 0)
+                .filter(|p| board.nuisance_neighbors(p).any(|n| self.test(&n)))
+                .map(|p| [p].into_iter().collect()),
+        )
+    }
+    ///How many times each piece of hard nuisance is hit by these popped groups,
+    /// one hit for every group next to it.
+    ///
+    /// Hard nuisance is left behind by `owanimo_nuisance`, pass these hits to `NuisanceBoard::damage` instead.
+    pub fn owanimo_hits<B: NuisanceBoard + Board<Handle = H>>(&self, board: &B) -> Vec<(H, u32)> {
+        board
+            .tiles()
+            .filter(|p| board.nuisance(p) && !board.block(p) && board.hit_points(p) > 0)
+            .filter_map(|p| {
+                let mut hit_by: Vec<GroupId> = board
+                    .nuisance_neighbors(&p)
+                    .filter_map(|n| self.group_of(&n))
+                    .collect();
+                hit_by.sort();
+                hit_by.dedup();
+                (!hit_by.is_empty()).then_some((p, hit_by.len() as u32))
+            })
             .collect()
     }
 }
@@ -0,0 +1,53 @@
+use alloc::vec::Vec;
+
+///A disjoint-set forest over the indices `0..len`, used to find groups of beings quickly.
+///
+/// Uses union by size and path halving, so every operation is close to constant time.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    ///Makes a forest where every index is in a set of its own.
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parent: (0..len).collect(),
+            size: alloc::vec![1; len],
+        }
+    }
+    ///How many indices this forest covers.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    ///Finds the representative of the set containing `index`.
+    pub fn find(&mut self, mut index: usize) -> usize {
+        while self.parent[index] != index {
+            self.parent[index] = self.parent[self.parent[index]];
+            index = self.parent[index];
+        }
+        index
+    }
+    ///Joins the sets containing `a` and `b`, returning the representative of the joined set.
+    pub fn union(&mut self, a: usize, b: usize) -> usize {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return a;
+        }
+        if self.size[a] < self.size[b] {
+            core::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        a
+    }
+    ///How many indices are in the same set as `index`.
+    pub fn set_len(&mut self, index: usize) -> usize {
+        let root = self.find(index);
+        self.size[root]
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    BanishBoard, Board, PlaceBoard,
+    gravity::GravityBoard,
+    grid::{CartesianBoard, GridTileBoard, tile_hooks},
+    quicksim::QuickSimBoard,
+};
+
+///Wraps a grid board so that its left and right edges (and optionally its top and bottom edges) touch,
+/// like the surface of a torus.
+///
+/// Groups that cross the seam count as one group, and nuisance next to the seam is banished from both sides.
+/// Gravity is left to the inner board, so beings still fall down their columns.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Wrapping<B> {
+    pub inner: B,
+    ///Do the left and right edges touch?
+    pub wrap_x: bool,
+    ///Do the top and bottom edges touch?
+    pub wrap_y: bool,
+}
+
+impl<B: CartesianBoard> Wrapping<B> {
+    ///Wraps the left and right edges of `inner`.
+    pub fn horizontal(inner: B) -> Self {
+        Wrapping {
+            inner,
+            wrap_x: true,
+            wrap_y: false,
+        }
+    }
+    ///Wraps both pairs of edges of `inner`.
+    pub fn torus(inner: B) -> Self {
+        Wrapping {
+            inner,
+            wrap_x: true,
+            wrap_y: true,
+        }
+    }
+    ///The neighbors across the seams, which the inner board doesn't know about.
+    fn seam_neighbors(&self, (x, y): (usize, usize)) -> [Option<(usize, usize)>; 2] {
+        let (w, h) = (self.inner.width(), self.inner.height());
+        //on boards this thin, the tile across the seam is already a neighbor
+        let across = |at: usize, len: usize| match at {
+            _ if len <= 2 => None,
+            0 => Some(len - 1),
+            at if at == len - 1 => Some(0),
+            _ => None,
+        };
+        [
+            across(x, w).filter(|_| self.wrap_x).map(|x| (x, y)),
+            across(y, h).filter(|_| self.wrap_y).map(|y| (x, y)),
+        ]
+    }
+}
+
+impl<B: CartesianBoard + GridTileBoard> Board for Wrapping<B> {
+    type Handle = (usize, usize);
+
+    fn tiles(&self) -> impl Iterator<Item = Self::Handle> {
+        self.inner.tiles()
+    }
+
+    fn neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.inner
+            .neighbors(handle)
+            .chain(self.seam_neighbors(*handle).into_iter().flatten())
+    }
+
+    fn nuisance_neighbors(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.inner
+            .nuisance_neighbors(handle)
+            .chain(self.seam_neighbors(*handle).into_iter().flatten())
+    }
+
+    tile_hooks!();
+}
+
+impl<B: CartesianBoard + GridTileBoard> CartesianBoard for Wrapping<B> {
+    fn width(&self) -> usize {
+        self.inner.width()
+    }
+    fn height(&self) -> usize {
+        self.inner.height()
+    }
+}
+
+impl<B: CartesianBoard + GridTileBoard> GridTileBoard for Wrapping<B> {
+    type Tile = B::Tile;
+    fn tile(&self, handle: &Self::Handle) -> B::Tile {
+        self.inner.tile(handle)
+    }
+    fn set_tile(&mut self, handle: &Self::Handle, tile: B::Tile) {
+        self.inner.set_tile(handle, tile)
+    }
+}
+
+impl<B: CartesianBoard + GridTileBoard + BanishBoard> BanishBoard for Wrapping<B> {
+    fn banish(&mut self, handle: Self::Handle) {
+        self.inner.banish(handle)
+    }
+    fn blast(&self, handle: &Self::Handle) -> impl Iterator<Item = Self::Handle> {
+        self.inner.blast(handle)
+    }
+}
+
+impl<B: CartesianBoard + GridTileBoard + PlaceBoard> PlaceBoard for Wrapping<B> {
+    type Being = B::Being;
+    fn place(&mut self, handle: Self::Handle, being: B::Being) {
+        self.inner.place(handle, being)
+    }
+    ///Steps off an edge that wraps come back in on the other side.
+    fn step(&self, &(x, y): &Self::Handle, (dx, dy): (isize, isize)) -> Option<Self::Handle> {
+        let wrap = |at: usize, by: isize, len: usize, wraps: bool| {
+            if wraps && len > 0 {
+                Some((at as isize + by).rem_euclid(len as isize) as usize)
+            } else {
+                at.checked_add_signed(by).filter(|&at| at < len)
+            }
+        };
+        Some((
+            wrap(x, dx, self.inner.width(), self.wrap_x)?,
+            wrap(y, dy, self.inner.height(), self.wrap_y)?,
+        ))
+    }
+}
+
+impl<B: CartesianBoard + GridTileBoard + GravityBoard> GravityBoard for Wrapping<B> {
+    fn fall(&mut self) -> bool {
+        self.inner.fall()
+    }
+    fn fall_tracked(&mut self, changed: &mut impl Extend<<Self as Board>::Handle>) -> bool {
+        self.inner.fall_tracked(changed)
+    }
+}
+
+impl<B: CartesianBoard + GridTileBoard + BanishBoard + GravityBoard> QuickSimBoard for Wrapping<B> {}
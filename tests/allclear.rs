@@ -0,0 +1,68 @@
+use owanimo::{
+    Board,
+    bitboard::BitBoard,
+    garbage::GarbageCalculator,
+    quicksim::{AllClearBonus, AllClearReward, QuickSimBoard, SimResult},
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::Grid;
+
+fn sim(board: &str) -> Result<SimResult, Box<dyn std::error::Error>> {
+    let mut board = board.parse::<Grid>()?;
+    Ok(board.quick_sim(&4, &TrivialPiecesCleared, &(), &[0, 8], &(), &()))
+}
+
+#[test]
+fn all_clear_is_detected() -> Result<(), Box<dyn std::error::Error>> {
+    assert!(sim("rrrro")?.all_clear);
+    assert!(!sim("rrrrb")?.all_clear);
+    //an empty board didn't chain, so it doesn't count
+    assert!(!sim("")?.all_clear);
+    assert!(Grid::new().all_clear());
+    assert!(BitBoard::default().all_clear());
+    assert!(!"r".parse::<BitBoard>()?.all_clear());
+    Ok(())
+}
+
+#[test]
+fn tsu_bonus_waits_for_the_next_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut bonus = AllClearBonus::tsu();
+    let mut cleared = sim("rrrr")?;
+    assert!(!bonus.apply(&mut cleared));
+    assert_eq!(cleared.bonus_garbage, 0);
+    //placing pieces without a chain keeps the bonus waiting
+    let mut nothing = sim("rb")?;
+    assert!(!bonus.apply(&mut nothing));
+    let mut next = sim("_bbb\nrrrrbr")?;
+    assert!(bonus.apply(&mut next));
+    assert_eq!(next.bonus_garbage, 30);
+    //a double worth 320 points is 4 nuisance, and the bonus goes on top
+    assert_eq!(next.link_scores, vec![0, 320]);
+    assert_eq!(
+        GarbageCalculator::default().add_links(next.link_scores.clone()),
+        4
+    );
+    assert_eq!(GarbageCalculator::default().add_sim(&next), 4 + 30);
+    //it's only handed out once
+    let mut after = sim("bbbbr")?;
+    assert!(!bonus.apply(&mut after));
+    Ok(())
+}
+
+#[test]
+fn score_bonus_straight_away() -> Result<(), Box<dyn std::error::Error>> {
+    let mut bonus = AllClearBonus::new(AllClearReward::Score(2100), false);
+    let plain = sim("rrrr")?;
+    let mut cleared = sim("rrrr")?;
+    assert!(bonus.apply(&mut cleared));
+    assert_eq!(cleared.score, plain.score + 2100);
+    assert_eq!(cleared.link_scores.last(), Some(&(plain.score + 2100)));
+    assert_eq!(
+        GarbageCalculator::default().add_sim(&cleared),
+        (plain.score + 2100) / 70
+    );
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use owanimo::{
+    Board,
+    bitboard::{BitBoard, Cell, CellSet, Tile},
+    gravity::GravityBoard,
+    quicksim::QuickSimBoard,
+    standard::{
+        ColorBoard, ColorBonusTable, ColoredPiecesCleared, GroupBonusTable, TrivialPiecesCleared,
+    },
+};
+
+mod common;
+
+use common::sorted_groups;
+
+const CHAIN: &str = "
+    orbg
+    rbgy
+    rbgyo
+    rbgyy
+";
+
+#[test]
+fn parse_and_get() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        ___
+        ypo
+        rgb
+    "
+    .parse::<BitBoard>()?;
+    assert_eq!(board.get(Cell::new(0, 0)), Tile::Red);
+    assert_eq!(board.get(Cell::new(2, 1)), Tile::Nuisance);
+    assert_eq!(board.get(Cell::new(3, 0)), Tile::Air);
+    assert_eq!(board.color(&Cell::new(1, 1)), Some(Tile::Purple));
+    assert_eq!(board.color(&Cell::new(2, 1)), None);
+    Ok(())
+}
+
+#[test]
+fn bit_grouping_matches_generic_grouping() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+    orbg
+    rbgyr
+    rbgyob
+    rbgyyb
+    rrppgb
+    "
+    .parse::<BitBoard>()?;
+    let bits = board.owanimo_grouper_in::<CellSet>();
+    let generic = board.owanimo_grouper_naive();
+    assert_eq!(sorted_groups(&bits), sorted_groups(&generic));
+    Ok(())
+}
+
+#[test]
+fn bit_fall() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+    r_b
+    _g_
+    __y
+    "
+    .parse::<BitBoard>()?;
+    let mut changed = CellSet::default();
+    assert!(board.fall_tracked(&mut changed));
+    assert_eq!(board, "__b\nrgy".parse::<BitBoard>()?);
+    assert_eq!(changed.0.count_ones(), 6);
+    assert!(!board.fall());
+    Ok(())
+}
+
+#[test]
+fn bit_quick_sim_matches_fast_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = CHAIN.parse::<BitBoard>()?;
+    let mut fast = board;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 4);
+    assert_eq!(result.pieces_cleared, 18);
+    assert_eq!(result.score, 180);
+    assert_eq!(board, BitBoard::default());
+    let fast_result = fast.fast_chain(4);
+    assert_eq!(fast_result.chain, 4);
+    assert_eq!(fast_result.pieces_cleared, 18);
+    assert_eq!(fast, BitBoard::default());
+    Ok(())
+}
+
+#[test]
+fn bit_fall_through_gaps() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+    r_____
+    g_____
+    ___b__
+    b__o_y
+    _______
+    y__r_o
+    ______
+    "
+    .parse::<BitBoard>()?;
+    assert!(board.fall());
+    assert_eq!(
+        board,
+        "
+        r
+        g__b
+        b__o_y
+        y__r_o
+        "
+        .parse::<BitBoard>()?
+    );
+    assert!(!board.fall());
+    Ok(())
+}
+
+#[test]
+fn fast_sim_matches_quick_sim() -> Result<(), Box<dyn std::error::Error>> {
+    let chain_power = [0, 8, 16, 32, 64, 96];
+    let color_bonus = [0, 0, 3, 6, 12, 24];
+    let group_bonus = [0, 0, 0, 0, 0, 2, 3, 4, 5, 6, 7, 10];
+    for drawn in [CHAIN, "rrrro\ngggbbb\nbgyyyy", "oo\nrrbb\nrrbb"] {
+        let mut board = drawn.parse::<BitBoard>()?;
+        let mut fast = board;
+        let slow = board.quick_sim(
+            &4,
+            &ColoredPiecesCleared,
+            &(),
+            &chain_power,
+            &ColorBonusTable {
+                table: &color_bonus,
+            },
+            &GroupBonusTable {
+                table: &group_bonus,
+            },
+        );
+        let quick = fast.fast_sim(4, &chain_power, &color_bonus, &group_bonus);
+        assert_eq!(quick.score, slow.score);
+        assert_eq!(quick.chain, slow.chain);
+        assert_eq!(quick.pieces_cleared, slow.pieces_cleared);
+        assert_eq!(quick.max_pieces_at_once, slow.max_pieces_at_once);
+        assert_eq!(quick.link_scores, slow.link_scores);
+        assert_eq!(quick.all_clear, slow.all_clear);
+        assert_eq!(quick.bonus_garbage, slow.bonus_garbage);
+        assert_eq!(fast, board);
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use owanimo::{
+    Board, gravity::GravityBoard, quicksim::QuickSimBoard, standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::{Grid, Tile, group_size};
+
+#[test]
+fn blocks_never_pop() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "ss".parse::<Grid>()?;
+    let groups = board.owanimo_grouper();
+    let groups = groups.as_ref();
+    assert!(!groups.owanimo_pop_by(&board, &1).test(&(0, 0)));
+    assert!(!board.connects(&(0, 0), &(1, 0)));
+    Ok(())
+}
+
+#[test]
+fn iron_holds_up_beings() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        r_s
+        i
+        _
+    "
+    .parse::<Grid>()?;
+    assert!(board.fall());
+    assert_eq!(board, "r\ni\n__s".parse::<Grid>()?);
+    assert!(!board.fall());
+    Ok(())
+}
+
+#[test]
+fn blocks_survive_a_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        rro
+        rrs
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    //the nuisance goes with the reds, but the stone stays put
+    assert_eq!(result.pieces_cleared, 5);
+    assert_eq!(board, "__s".parse::<Grid>()?);
+    Ok(())
+}
+
+#[test]
+fn coloured_blocks_stay_out_of_groups() -> Result<(), Box<dyn std::error::Error>> {
+    //the red stones connect to the reds, but they must not join them into a group of 9
+    let board = "
+        SSSS
+        rrSrr
+    "
+    .parse::<Grid>()?;
+    assert_eq!(group_size(&board, (0, 0)), 2);
+    assert_eq!(group_size(&board, (2, 0)), 1);
+    assert_eq!(group_size(&board, (0, 1)), 1);
+    let groups = board.owanimo_grouper();
+    let groups = groups.as_ref();
+    assert!(groups.owanimo_pop(4).is_empty());
+    Ok(())
+}
+
+#[test]
+fn coloured_blocks_stay_out_of_regrouped_chains() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        _Srr
+        _rbb
+    "
+    .parse::<Grid>()?;
+    let mut groups = board.owanimo_grouper();
+    //the new red touches a red that only touches the other two through a red stone
+    board.set((0, 0), Tile::Red);
+    board.owanimo_regroup(&mut groups, [(0, 0)]);
+    assert_eq!(groups.find(&(0, 0)).map(|g| g.len()), Some(2));
+    assert_eq!(groups.find(&(1, 1)).map(|g| g.len()), Some(1));
+    assert_eq!(groups.find(&(2, 1)).map(|g| g.len()), Some(2));
+    Ok(())
+}
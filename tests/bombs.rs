@@ -0,0 +1,58 @@
+use owanimo::{
+    quicksim::QuickSimBoard,
+    standard::{ColorBonusTable, GroupBonusTable, TrivialPiecesCleared},
+};
+
+mod common;
+
+use common::Grid;
+
+#[test]
+fn bombs_blast_their_surroundings() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        ____sg
+        rrrrxg
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    //the bomb takes out the green next to it, but not the one on the diagonal or the stone
+    assert_eq!(result.pieces_cleared, 6);
+    assert_eq!(board, "____sg".parse::<Grid>()?);
+    Ok(())
+}
+
+#[test]
+fn bombs_set_off_bombs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        ____gg
+        rrrr+x
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    assert_eq!(result.pieces_cleared, 8);
+    assert!(board.is_empty());
+    Ok(())
+}
+
+#[test]
+fn blasts_get_no_bonuses() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "rrrXg".parse::<Grid>()?;
+    let result = board.quick_sim(
+        &4,
+        &TrivialPiecesCleared,
+        &(),
+        &[1],
+        &ColorBonusTable { table: &[0, 0, 3] },
+        &GroupBonusTable {
+            table: &[0, 1, 0, 0, 0],
+        },
+    );
+    assert_eq!(result.chain, 1);
+    //the green caught in the blast is cleared, but it doesn't count as another colour or another group
+    assert_eq!(result.pieces_cleared, 5);
+    assert_eq!(result.score, 50);
+    assert!(board.is_empty());
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//!Helpers shared by the integration tests, pull them in with `mod common;`.
+//!
+//! Every test only uses the helpers and beings it needs, so the rest go unused.
+#![allow(dead_code)]
+
+use owanimo::{
+    Board, BoardHandle, GroupSet, Groups,
+    grid::{Blast, GridBoard, GridTile, NuisanceTile},
+};
+
+///Every group as a sorted list of handles, sorted, so that groups found in a different order still compare equal.
+pub fn sorted_groups<H: BoardHandle + Ord, S: GroupSet<H>>(groups: &Groups<H, S>) -> Vec<Vec<H>> {
+    let mut groups = groups
+        .iter()
+        .map(|g| {
+            let mut g = g.handles().collect::<Vec<_>>();
+            g.sort();
+            g
+        })
+        .collect::<Vec<_>>();
+    groups.sort();
+    groups
+}
+
+///How big the group with the tile at `of` in it is, or 0 if it isn't in one.
+pub fn group_size<B: Board>(board: &B, of: B::Handle) -> usize {
+    let groups = board.owanimo_grouper();
+    groups
+        .group_of(&of)
+        .and_then(|id| groups.get(id))
+        .map_or(0, |group| group.len())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Air,
+    Nuisance,
+    ///Hard nuisance, with the hits it can take before it turns into regular nuisance.
+    Hard(u32),
+    Point,
+    Sun,
+    Stone,
+    Iron,
+    ///A stone that still has a colour, so only the grouper keeps it out of groups.
+    RedStone,
+    Rainbow,
+    Bomb,
+    CrossBomb,
+    ///A bomb that still has a colour, so it pops along with the reds.
+    RedBomb,
+    Red,
+    Green,
+    Blue,
+    Yellow,
+}
+
+impl GridTile for Tile {
+    type Color = Tile;
+    fn air() -> Self {
+        Tile::Air
+    }
+    fn is_air(&self) -> bool {
+        *self == Tile::Air
+    }
+    fn is_nuisance(&self) -> bool {
+        matches!(
+            self,
+            Tile::Nuisance | Tile::Hard(_) | Tile::Point | Tile::Sun | Tile::Bomb | Tile::CrossBomb
+        )
+    }
+    fn color(&self) -> Option<Tile> {
+        match self {
+            Tile::Red | Tile::RedStone | Tile::RedBomb => Some(Tile::Red),
+            Tile::Green | Tile::Blue | Tile::Yellow => Some(*self),
+            _ => None,
+        }
+    }
+    fn hit_points(&self) -> u32 {
+        match self {
+            Tile::Hard(hp) => *hp,
+            _ => 0,
+        }
+    }
+    fn damage(&mut self, hits: u32) {
+        if let Tile::Hard(hp) = self {
+            *self = match hp.saturating_sub(hits) {
+                0 => Tile::Nuisance,
+                left => Tile::Hard(left),
+            };
+        }
+    }
+    fn points(&self) -> u64 {
+        match self {
+            Tile::Point => 50,
+            _ => 0,
+        }
+    }
+    fn is_sun(&self) -> bool {
+        *self == Tile::Sun
+    }
+    fn is_block(&self) -> bool {
+        matches!(self, Tile::Stone | Tile::Iron | Tile::RedStone)
+    }
+    fn is_anchored(&self) -> bool {
+        *self == Tile::Iron
+    }
+    fn blast(&self) -> Option<Blast> {
+        match self {
+            Tile::Bomb | Tile::RedBomb => Some(Blast::Radius(1)),
+            Tile::CrossBomb => Some(Blast::Cross),
+            _ => None,
+        }
+    }
+    fn is_wildcard(&self) -> bool {
+        *self == Tile::Rainbow
+    }
+}
+
+impl NuisanceTile for Tile {
+    fn nuisance() -> Self {
+        Tile::Nuisance
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = ();
+    fn try_from(c: char) -> Result<Self, ()> {
+        Ok(match c {
+            '_' | ' ' => Tile::Air,
+            'o' => Tile::Nuisance,
+            'h' => Tile::Hard(1),
+            'H' => Tile::Hard(2),
+            'p' => Tile::Point,
+            '*' => Tile::Sun,
+            's' => Tile::Stone,
+            'i' => Tile::Iron,
+            'S' => Tile::RedStone,
+            'w' => Tile::Rainbow,
+            'x' => Tile::Bomb,
+            '+' => Tile::CrossBomb,
+            'X' => Tile::RedBomb,
+            'r' => Tile::Red,
+            'g' => Tile::Green,
+            'b' => Tile::Blue,
+            'y' => Tile::Yellow,
+            _ => return Err(()),
+        })
+    }
+}
+
+///The usual 6 wide and 12 tall board.
+pub type Grid = GridBoard<Tile, 6, 12>;
@@ -0,0 +1,74 @@
+use owanimo::{
+    Board,
+    connect::{Connected, KNIGHT, Neighborhood},
+    quicksim::QuickSimBoard,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::{Grid, group_size};
+
+#[test]
+fn diagonal_groups() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        ___r
+        __rg
+        _rg
+        rg
+    "
+    .parse::<Grid>()?;
+    assert_eq!(group_size(&board, (0, 0)), 1);
+    let eight = Connected::new(board, Neighborhood::Eight);
+    assert_eq!(group_size(&eight, (0, 0)), 4);
+    assert_eq!(group_size(&eight, (1, 0)), 3);
+    assert_eq!(eight.neighbors(&(0, 0)).count(), 3);
+    Ok(())
+}
+
+#[test]
+fn knight_links() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        __g
+        _g
+        ___g
+        g
+    "
+    .parse::<Grid>()?;
+    let knight = Connected::new(board, Neighborhood::Offsets(&KNIGHT));
+    //(0,0), (1,2), (3,1) and (2,3) are each a knight's move from the last, without touching at all
+    assert_eq!(group_size(&knight, (0, 0)), 4);
+    Ok(())
+}
+
+#[test]
+fn nuisance_clears_orthogonally() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        ___r
+        __rbo
+        _rgyb
+        rgbgy
+    "
+    .parse::<Grid>()?;
+    let mut orthogonal = Connected {
+        nuisance: Neighborhood::Four,
+        ..Connected::new(board, Neighborhood::Eight)
+    };
+    let result = orthogonal.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    //the nuisance only touches the reds through a corner
+    assert_eq!(result.pieces_cleared, 4);
+    assert_eq!(
+        orthogonal.inner,
+        "
+        ___bo
+        __gyb
+        _gbgy
+        "
+        .parse::<Grid>()?
+    );
+    let mut diagonal = Connected::new(board, Neighborhood::Eight);
+    let result = diagonal.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.pieces_cleared, 5);
+    Ok(())
+}
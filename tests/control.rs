@@ -0,0 +1,93 @@
+use owanimo::{
+    control::{ActivePair, Orientation},
+    pieces::Piece,
+};
+
+mod common;
+
+use common::{Grid, Tile};
+
+#[test]
+fn shifts_stop_at_walls_and_beings() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "____o".parse::<Grid>()?;
+    let mut pair = ActivePair::new(Tile::Red, Tile::Green, (0, 10));
+    assert!(!pair.shift_left(&board));
+    while pair.shift_right(&board) {}
+    assert_eq!(pair.position, (5, 10));
+    let mut low = ActivePair::new(Tile::Red, Tile::Green, (2, 0));
+    assert_eq!(pair.satellite_position(&board), Some((5, 11)));
+    assert!(low.shift_right(&board));
+    assert!(!low.shift_right(&board));
+    assert_eq!(low.position, (3, 0));
+    Ok(())
+}
+
+#[test]
+fn kicks_off_walls_and_floor() {
+    let board = Grid::new();
+    let mut pair = ActivePair::new(Tile::Red, Tile::Green, (5, 5));
+    assert!(pair.rotate_cw(&board));
+    assert_eq!(pair.orientation, Orientation::Right);
+    assert_eq!(pair.position, (4, 5));
+
+    let mut pair = ActivePair::new(Tile::Red, Tile::Green, (2, 0));
+    assert!(pair.rotate_ccw(&board));
+    assert!(pair.rotate_ccw(&board));
+    assert_eq!(pair.orientation, Orientation::Down);
+    assert_eq!(pair.position, (2, 1));
+    assert_eq!(pair.satellite_position(&board), Some((2, 0)));
+}
+
+#[test]
+fn quick_turn_in_a_narrow_well() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        o_o
+        o_o
+        o_o
+        o_o
+        o_o
+        o_o
+        o_o
+    "
+    .parse::<Grid>()?;
+    let mut pair = ActivePair::new(Tile::Red, Tile::Green, (1, 4));
+    assert!(!pair.rotate_cw(&board));
+    assert!(pair.quick_turn);
+    assert!(pair.rotate_ccw(&board));
+    assert_eq!(pair.orientation, Orientation::Down);
+    assert_eq!(pair.satellite_position(&board), Some((1, 3)));
+    Ok(())
+}
+
+#[test]
+fn no_quick_turn_with_one_side_open() -> Result<(), Box<dyn std::error::Error>> {
+    //lying on the floor under a ledge, so it can't turn either way, but the left is open
+    let board = "_o\n___".parse::<Grid>()?;
+    let mut pair = ActivePair::new(Tile::Red, Tile::Green, (1, 0));
+    assert!(pair.rotate_cw(&board));
+    assert_eq!(pair.orientation, Orientation::Right);
+    assert!(!pair.rotate_cw(&board));
+    assert!(!pair.quick_turn);
+    assert!(!pair.rotate_ccw(&board));
+    assert_eq!(pair.orientation, Orientation::Right);
+    Ok(())
+}
+
+#[test]
+fn soft_drop_and_lock() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "r".parse::<Grid>()?;
+    let piece = Piece::Pair {
+        pivot: Tile::Green,
+        satellite: Tile::Blue,
+    };
+    let mut pair = ActivePair::from_piece(piece, (0, 8)).ok_or("not a pair")?;
+    assert!(pair.rotate_cw(&board));
+    while pair.soft_drop(&board) {}
+    assert!(pair.landed(&board));
+    assert_eq!(pair.position, (0, 1));
+    pair.lock(&mut board);
+    //the satellite had nothing under it, so it splits off and falls
+    assert_eq!(board, "g\nrb".parse::<Grid>()?);
+    assert_eq!(ActivePair::from_piece(Piece::Big(Tile::Red), (0, 8)), None);
+    Ok(())
+}
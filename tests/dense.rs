@@ -0,0 +1,145 @@
+use owanimo::{
+    BanishBoard, Board,
+    dense::{BitSet, DenseHandle},
+    gravity::AutoGravityBoard,
+    quicksim::QuickSimBoard,
+    standard::{NuisanceBoard, TrivialPiecesCleared},
+};
+
+mod common;
+
+use common::sorted_groups;
+
+const WIDTH: usize = 6;
+const HEIGHT: usize = 12;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+struct Cell(u8);
+
+impl Cell {
+    fn new(x: usize, y: usize) -> Cell {
+        Cell((y * WIDTH + x) as u8)
+    }
+    fn x(self) -> usize {
+        self.0 as usize % WIDTH
+    }
+    fn y(self) -> usize {
+        self.0 as usize / WIDTH
+    }
+}
+
+impl DenseHandle for Cell {
+    const CAPACITY: usize = WIDTH * HEIGHT;
+    fn to_index(&self) -> usize {
+        self.0 as usize
+    }
+    fn from_index(index: usize) -> Self {
+        Cell(index as u8)
+    }
+}
+
+///Tiles are chars, `.` is air and `o` is nuisance.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct CharBoard {
+    cells: [char; WIDTH * HEIGHT],
+}
+
+impl CharBoard {
+    ///Rows are given top to bottom, the last row is `y = 0`.
+    fn new(rows: &[&str]) -> CharBoard {
+        let mut cells = ['.'; WIDTH * HEIGHT];
+        for (y, row) in rows.iter().rev().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                cells[Cell::new(x, y).0 as usize] = c;
+            }
+        }
+        CharBoard { cells }
+    }
+    fn get(&self, cell: Cell) -> char {
+        self.cells[cell.0 as usize]
+    }
+}
+
+impl Board for CharBoard {
+    type Handle = Cell;
+    fn tiles(&self) -> impl Iterator<Item = Cell> {
+        (0..Cell::CAPACITY).map(Cell::from_index)
+    }
+    fn neighbors(&self, handle: &Cell) -> impl Iterator<Item = Cell> {
+        let (x, y) = (handle.x(), handle.y());
+        [
+            (x > 0).then(|| Cell::new(x - 1, y)),
+            (y > 0).then(|| Cell::new(x, y - 1)),
+            (x < WIDTH - 1).then(|| Cell::new(x + 1, y)),
+            (y < HEIGHT - 1).then(|| Cell::new(x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+    fn connects(&self, a: &Cell, b: &Cell) -> bool {
+        let (a, b) = (self.get(*a), self.get(*b));
+        a == b && a != '.' && a != 'o'
+    }
+}
+
+impl NuisanceBoard for CharBoard {
+    fn nuisance(&self, handle: &Cell) -> bool {
+        self.get(*handle) == 'o'
+    }
+}
+
+impl BanishBoard for CharBoard {
+    fn banish(&mut self, handle: Cell) {
+        self.cells[handle.0 as usize] = '.';
+    }
+}
+
+impl AutoGravityBoard for CharBoard {
+    fn is_air(&self, handle: Cell) -> bool {
+        self.get(handle) == '.'
+    }
+    fn mutate_columns(&mut self, mut mutater: impl FnMut(&Self, &mut [Cell])) {
+        for x in 0..WIDTH {
+            let mut col: [Cell; HEIGHT] = core::array::from_fn(|y| Cell::new(x, y));
+            mutater(self, &mut col);
+            let original = self.cells;
+            for (y, from) in col.into_iter().enumerate() {
+                self.cells[Cell::new(x, y).0 as usize] = original[from.0 as usize];
+            }
+        }
+    }
+}
+
+impl QuickSimBoard<BitSet<Cell>> for CharBoard {}
+
+const CHAIN: &[&str] = &["orbg", "rbgy", "rbgyo", "rbgyy"];
+
+#[test]
+fn bitset_groups_match_hashset_groups() {
+    let board = CharBoard::new(&["rrb.y", "rbbgy", "ggbgo"]);
+    let dense = board.owanimo_grouper_in::<BitSet<Cell>>();
+    let hashed = board.owanimo_grouper();
+    assert_eq!(sorted_groups(&dense), sorted_groups(&hashed));
+    let blue = dense.group_of(&Cell::new(2, 0)).unwrap();
+    assert_eq!(dense.get(blue).map(|g| g.len()), Some(4));
+}
+
+#[test]
+fn bitset_nuisance_pass() {
+    let board = CharBoard::new(&["rrrro"]);
+    let groups = board.owanimo_grouper_in::<BitSet<Cell>>();
+    let binding = groups.as_ref();
+    let binding = binding.owanimo_pop(4);
+    let popped = binding.owanimo_nuisance(&board);
+    assert_eq!(popped.len(), 2);
+    assert!(popped.test(&Cell::new(4, 0)));
+}
+
+#[test]
+fn bitset_quick_sim() {
+    let mut board = CharBoard::new(CHAIN);
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 4);
+    assert_eq!(result.pieces_cleared, 18);
+    assert!(board.cells.iter().all(|&c| c == '.'));
+}
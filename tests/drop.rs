@@ -0,0 +1,58 @@
+use owanimo::{
+    grid::{GridTile, NuisanceDropBoard},
+    rng::{Rng, SplitMix64},
+};
+
+mod common;
+
+use common::Grid;
+
+fn column_heights(board: &Grid) -> [usize; 6] {
+    core::array::from_fn(|x| board.getcol(x).iter().filter(|t| !t.is_air()).count())
+}
+
+#[test]
+fn same_seed_same_drop() {
+    assert_eq!(SplitMix64::new(7).next_u64(), SplitMix64::new(7).next_u64());
+    let mut a = Grid::new();
+    let mut b = Grid::new();
+    assert_eq!(a.drop_nuisance(16, &mut SplitMix64::new(42)), 16);
+    assert_eq!(b.drop_nuisance(16, &mut SplitMix64::new(42)), 16);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn full_rows_spread_and_leftovers_never_share_a_column() {
+    for seed in 0..64 {
+        let mut board = Grid::new();
+        assert_eq!(board.drop_nuisance(17, &mut SplitMix64::new(seed)), 17);
+        let heights = column_heights(&board);
+        //two full rows, then five more spread over five different columns
+        assert_eq!(heights.iter().filter(|&&h| h == 3).count(), 5);
+        assert_eq!(heights.iter().filter(|&&h| h == 2).count(), 1);
+    }
+}
+
+#[test]
+fn nuisance_lands_on_the_stack() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        i
+        _
+        rrrrrr
+    "
+    .parse::<Grid>()?;
+    //the column under the iron is cut off from the sky
+    assert_eq!(board.drop_nuisance(12, &mut SplitMix64::new(1)), 12);
+    assert_eq!(
+        board,
+        "
+        o
+        o
+        iooooo
+        _ooooo
+        rrrrrr
+        "
+        .parse::<Grid>()?
+    );
+    Ok(())
+}
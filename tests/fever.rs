@@ -0,0 +1,129 @@
+use owanimo::{
+    ParseError,
+    fever::{BookError, Fever, FeverBook, FeverRules},
+    quicksim::QuickSimBoard,
+    rng::SplitMix64,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::Grid;
+
+const BOOK: &str = "
+[1]
+rrrr
+
+[2]
+b
+r
+r
+rb
+rbb
+";
+
+fn sim(board: &mut Grid) -> owanimo::quicksim::SimResult {
+    board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &())
+}
+
+#[test]
+fn parse_book() -> Result<(), Box<dyn std::error::Error>> {
+    let book = BOOK.parse::<FeverBook<Grid>>()?;
+    assert_eq!(book.boards.len(), 2);
+    assert_eq!(book.boards[0], (1, "rrrr".parse()?));
+    let mut rng = SplitMix64::new(0);
+    //there's no 5 chain, so the longest shorter one is used
+    assert_eq!(book.pick(5, &mut rng), Some(&book.boards[1].1));
+    assert_eq!(book.pick(0, &mut rng), Some(&book.boards[0].1));
+    assert_eq!(
+        "rrrr\n[1]".parse::<FeverBook<Grid>>(),
+        Err(BookError::BadHeader { line: 1 })
+    );
+    assert_eq!(
+        "[1]\nrrrr\n[one]".parse::<FeverBook<Grid>>(),
+        Err(BookError::BadHeader { line: 3 })
+    );
+    assert_eq!(
+        "[1]\nr?".parse::<FeverBook<Grid>>(),
+        Err(BookError::Board(ParseError::UnknownSymbol {
+            at: (1, 0),
+            symbol: '?'
+        }))
+    );
+    Ok(())
+}
+
+#[test]
+fn gauge_fills_from_offsets() {
+    let mut fever = Fever::<Grid>::new(FeverRules {
+        gauge_size: 2,
+        ..FeverRules::default()
+    });
+    assert!(!fever.add_offset());
+    assert!(fever.add_offset());
+    assert!(fever.add_offset());
+    assert_eq!(fever.gauge, 2);
+}
+
+#[test]
+fn fever_round() -> Result<(), Box<dyn std::error::Error>> {
+    let book = BOOK.parse::<FeverBook<Grid>>()?;
+    let mut rng = SplitMix64::new(3);
+    let mut fever = Fever::new(FeverRules {
+        gauge_size: 1,
+        start_chain: 2,
+        min_chain: 1,
+        max_chain: 3,
+        start_time: 100,
+        time_per_link: 10,
+        max_time: 150,
+    });
+    let normal = "ggg".parse::<Grid>()?;
+    let mut board = normal;
+    assert!(!fever.start(&mut board, &book, &mut rng));
+    fever.add_offset();
+    assert!(fever.start(&mut board, &book, &mut rng));
+    assert!(fever.is_active());
+    assert_eq!(fever.gauge, 0);
+
+    //the 2 chain goes off in full
+    let result = sim(&mut board);
+    assert_eq!(result.chain, 2);
+    assert!(fever.after_chain(&mut board, &result, &book, &mut rng));
+    assert_eq!(fever.chain_length, 3);
+    assert_eq!(fever.time_left, 120);
+    assert_eq!(board, book.boards[1].1);
+
+    //only a 2 chain, but a 3 was wanted
+    let result = sim(&mut board);
+    assert!(!fever.after_chain(&mut board, &result, &book, &mut rng));
+    assert_eq!(fever.chain_length, 2);
+    assert_eq!(fever.time_left, 140);
+
+    assert!(!fever.tick(&mut board, 100));
+    assert!(fever.tick(&mut board, 100));
+    assert!(!fever.is_active());
+    assert_eq!(board, normal);
+    Ok(())
+}
+
+#[test]
+fn chain_limits_the_wrong_way_round() -> Result<(), Box<dyn std::error::Error>> {
+    let book = BOOK.parse::<FeverBook<Grid>>()?;
+    let mut rng = SplitMix64::new(0);
+    let mut fever = Fever::new(FeverRules {
+        gauge_size: 1,
+        start_chain: 1,
+        min_chain: 5,
+        max_chain: 3,
+        ..FeverRules::default()
+    });
+    let mut board = Grid::new();
+    fever.add_offset();
+    assert!(fever.start(&mut board, &book, &mut rng));
+    let result = sim(&mut board);
+    assert!(fever.after_chain(&mut board, &result, &book, &mut rng));
+    //`max_chain` wins instead of panicking
+    assert_eq!(fever.chain_length, 3);
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use owanimo::{
+    garbage::{FEVER_TARGET, GarbageCalculator, GarbageQueue, OffsetRule, Rounding, TSU_TARGET},
+    quicksim::QuickSimBoard,
+    standard::{ColorBonusTable, TrivialPiecesCleared},
+};
+
+mod common;
+
+use common::Grid;
+
+#[test]
+fn leftovers_carry_over() {
+    let mut calc = GarbageCalculator::new(TSU_TARGET);
+    assert_eq!(calc.add_link(40), 0);
+    assert_eq!(calc.leftover, 40);
+    assert_eq!(calc.add_link(320), 5);
+    assert_eq!(calc.leftover, 10);
+    //and into the next turn
+    assert_eq!(calc.add_links([60, 140]), 3);
+    assert_eq!(calc.leftover, 0);
+}
+
+#[test]
+fn rounding() {
+    let mut nearest = GarbageCalculator {
+        rounding: Rounding::Nearest,
+        ..GarbageCalculator::new(TSU_TARGET)
+    };
+    assert_eq!(nearest.add_link(100), 1);
+    assert_eq!(nearest.add_link(35), 1);
+    assert_eq!(nearest.leftover, -5);
+    let mut up = GarbageCalculator {
+        rounding: Rounding::Up,
+        ..GarbageCalculator::new(TSU_TARGET)
+    };
+    assert_eq!(up.add_link(71), 2);
+    assert_eq!(up.leftover, -69);
+    assert_eq!(up.add_link(69), 0);
+    assert_eq!(up.leftover, 0);
+}
+
+#[test]
+fn garbage_from_quick_sim() -> Result<(), Box<dyn std::error::Error>> {
+    let start = "
+        _b
+        rg
+        rgb
+        rgb
+        rgb
+    "
+    .parse::<Grid>()?;
+    let mut board = start;
+    let result = board.quick_sim(
+        &4,
+        &TrivialPiecesCleared,
+        &(),
+        &[0, 8],
+        &ColorBonusTable { table: &[0, 0, 3] },
+        &(),
+    );
+    assert_eq!(result.link_scores, [240, 320]);
+    assert_eq!(GarbageCalculator::new(TSU_TARGET).add_sim(&result), 3 + 5);
+    let mut fever = GarbageCalculator::new(FEVER_TARGET);
+    assert_eq!(fever.add_sim(&result), 2 + 2);
+    assert_eq!(fever.leftover, 80);
+    Ok(())
+}
+
+#[test]
+fn tsu_counter_attack() {
+    let mut me = GarbageQueue::new(OffsetRule::Tsu);
+    let mut them = GarbageQueue::new(OffsetRule::Tsu);
+    them.send(&mut me, 10);
+    them.end_chain(&mut me);
+    them.send(&mut me, 4);
+    assert_eq!((me.confirmed, me.pending), (10, 4));
+    //confirmed nuisance goes first, then the excess is sent straight back
+    me.send(&mut them, 12);
+    assert_eq!((me.confirmed, me.pending), (0, 2));
+    me.send(&mut them, 5);
+    assert_eq!(me.incoming(), 0);
+    assert_eq!(them.pending, 3);
+    me.end_chain(&mut them);
+    assert_eq!(them.take_drop(30), 3);
+}
+
+#[test]
+fn fever_counter_attack() {
+    let mut me = GarbageQueue::new(OffsetRule::Fever);
+    let mut them = GarbageQueue::new(OffsetRule::Fever);
+    them.send(&mut me, 6);
+    them.end_chain(&mut me);
+    me.send(&mut them, 4);
+    me.send(&mut them, 7);
+    //the excess waits for the end of the chain
+    assert_eq!(me.incoming(), 0);
+    assert_eq!(them.incoming(), 0);
+    me.end_chain(&mut them);
+    assert_eq!(them.confirmed, 5);
+    assert_eq!(them.take_drop(3), 3);
+    assert_eq!(them.confirmed, 2);
+}
+
+#[test]
+fn trades_are_symmetric() {
+    let mut a = GarbageQueue::new(OffsetRule::Tsu);
+    let mut b = GarbageQueue::new(OffsetRule::Tsu);
+    a.receive(5);
+    GarbageQueue::trade(&mut a, 20, &mut b, 8);
+    let mut b2 = GarbageQueue::new(OffsetRule::Tsu);
+    let mut a2 = GarbageQueue::new(OffsetRule::Tsu);
+    a2.receive(5);
+    GarbageQueue::trade(&mut b2, 8, &mut a2, 20);
+    assert_eq!((a, b), (a2, b2));
+    assert_eq!((a.incoming(), b.incoming()), (0, 7));
+}
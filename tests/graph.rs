@@ -0,0 +1,85 @@
+use owanimo::{
+    Board, graph::GraphBoard, gravity::GravityBoard, quicksim::QuickSimBoard,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::Tile;
+
+///Nodes `0..ring` in a ring.
+fn ring(nodes: usize) -> GraphBoard<Tile> {
+    GraphBoard::from_edges(nodes, (0..nodes).map(|i| (i, (i + 1) % nodes)))
+}
+
+#[test]
+fn graph_ring_groups_wrap_around() {
+    let mut board = ring(6);
+    for node in [4, 5, 0, 1] {
+        board.set(node, Tile::Red);
+    }
+    let groups = board.owanimo_grouper();
+    assert_eq!(groups.group_of(&4), groups.group_of(&1));
+    assert_eq!(groups.len(), 3);
+    assert_eq!(board.neighbors(&0).collect::<Vec<_>>(), vec![1, 5]);
+}
+
+#[test]
+fn graph_gravity_follows_falls_to_edges() {
+    //a column 0 <- 1 <- 2 <- 3, with 4 falling into it through a portal
+    let mut board = GraphBoard::from_edges(5, [(0, 1), (1, 2), (2, 3)]);
+    for node in 1..4 {
+        board.set_falls_to(node, Some(node - 1));
+    }
+    board.set_falls_to(4, Some(3));
+    board.set(2, Tile::Red);
+    board.set(4, Tile::Blue);
+    let mut changed = vec![];
+    assert!(board.fall_tracked(&mut changed));
+    assert_eq!(board.get(0), Tile::Red);
+    assert_eq!(board.get(1), Tile::Blue);
+    assert!(changed.contains(&4));
+    assert!(!board.fall());
+}
+
+#[test]
+fn graph_falls_to_loops_stop() {
+    let mut board = ring(3);
+    board.set_falls_to(0, Some(1));
+    board.set_falls_to(1, Some(0));
+    board.set(0, Tile::Red);
+    board.fall();
+    assert_eq!(
+        board.tiles().filter(|&n| board.get(n) == Tile::Red).count(),
+        1
+    );
+}
+
+#[test]
+fn graph_quick_sim() {
+    //an island of 4 nodes in a line, with a nuisance node hanging off the end
+    let mut board = GraphBoard::from_edges(5, [(0, 1), (1, 2), (2, 3), (3, 4)]);
+    for node in 0..4 {
+        board.set(node, Tile::Red);
+    }
+    board.set(4, Tile::Nuisance);
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    assert_eq!(result.pieces_cleared, 5);
+    assert!(board.tiles().all(|n| board.get(n) == Tile::Air));
+}
+
+#[test]
+fn graph_ignores_nodes_outside_the_board() {
+    let mut board = ring(3);
+    assert!(!board.add_edge(0, 3));
+    assert!(!board.set_falls_to(5, Some(0)));
+    assert!(!board.set_falls_to(0, Some(3)));
+    assert_eq!(board.falls_to(0), None);
+    assert_eq!(board.falls_to(7), None);
+    assert!(board.set_falls_to(1, Some(0)));
+    board.set(1, Tile::Red);
+    assert!(board.fall());
+    assert_eq!(board.get(0), Tile::Red);
+    assert_eq!(board.neighbors(&0).count(), 2);
+}
@@ -0,0 +1,67 @@
+use owanimo::{
+    Board,
+    gravity::GravityBoard,
+    quicksim::QuickSimBoard,
+    standard::{ColorBoard, GroupBonusTable, NuisanceBoard, TrivialPiecesCleared},
+};
+
+mod common;
+
+use common::{Grid, Tile};
+
+#[test]
+fn grid_parse_and_query() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        yo
+        rg
+    "
+    .parse::<Grid>()?;
+    assert_eq!(board.get((0, 1)), Tile::Yellow);
+    assert_eq!(board.color(&(0, 0)), Some(Tile::Red));
+    assert!(board.nuisance(&(1, 1)));
+    assert_eq!(board.get((7, 7)), Tile::Air);
+    assert_eq!(board.neighbors(&(5, 11)).count(), 2);
+    assert!("r?".parse::<Grid>().is_err());
+    Ok(())
+}
+
+#[test]
+fn grid_gravity() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        r_b
+        _g_
+        __y
+    "
+    .parse::<Grid>()?;
+    assert!(board.fall());
+    assert_eq!(board, "__b\nrgy".parse::<Grid>()?);
+    assert!(!board.fall());
+    Ok(())
+}
+
+#[test]
+fn grid_quick_sim() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+    orbg
+    rbgy
+    rbgyo
+    rbgyy
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(
+        &4,
+        &TrivialPiecesCleared,
+        &(),
+        &[1],
+        &(),
+        &GroupBonusTable {
+            table: &[0, 0, 0, 0, 2],
+        },
+    );
+    assert_eq!(result.chain, 4);
+    assert_eq!(result.pieces_cleared, 18);
+    //every link pops one group of 4, which gets a group bonus of 2
+    assert_eq!(result.score, 180 * 3);
+    assert!(board.is_empty());
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use owanimo::{
+    Board,
+    gravity::{AxisGravityBoard, GravityBoard},
+    grid3d::{Axis, Connectivity, Grid3D},
+    quicksim::QuickSimBoard,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::Tile;
+
+type Cube = Grid3D<Tile, 3, 3, 4>;
+
+#[test]
+fn grid3d_neighbor_counts() {
+    let centre = (1, 1, 1);
+    let mut board = Cube::default();
+    assert_eq!(board.neighbors(&centre).count(), 6);
+    board.connectivity = Connectivity::Edges;
+    assert_eq!(board.neighbors(&centre).count(), 18);
+    board.connectivity = Connectivity::Corners;
+    assert_eq!(board.neighbors(&centre).count(), 26);
+    assert_eq!(board.neighbors(&(0, 0, 0)).count(), 7);
+}
+
+#[test]
+fn grid3d_corner_groups() {
+    let mut board = Cube::default();
+    for handle in [(0, 0, 0), (1, 1, 1), (2, 2, 2), (2, 2, 3)] {
+        board.set(handle, Tile::Red);
+    }
+    assert!(board.owanimo_grouper().iter().all(|g| g.len() < 4));
+    board.connectivity = Connectivity::Corners;
+    assert!(board.owanimo_grouper().iter().any(|g| g.len() == 4));
+}
+
+#[test]
+fn grid3d_falls_along_any_axis() {
+    let mut board = Cube::default();
+    board.set((2, 1, 3), Tile::Red);
+    assert!(board.fall());
+    assert_eq!(board.get((2, 1, 0)), Tile::Red);
+    let mut changed = vec![];
+    assert!(board.fall_along(Axis::X, &mut changed));
+    assert_eq!(board.get((0, 1, 0)), Tile::Red);
+    assert_eq!(changed, vec![(2, 1, 0), (0, 1, 0)]);
+    assert!(board.fall_along(Axis::Y, &mut changed));
+    assert_eq!(board.get((0, 0, 0)), Tile::Red);
+    assert!(!board.fall());
+}
+
+#[test]
+fn grid3d_quick_sim() {
+    let mut board = Cube::default();
+    //a 2x2 layer of red, with blue stacked on top of it and beside it
+    for handle in [(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)] {
+        board.set(handle, Tile::Red);
+    }
+    for handle in [(0, 0, 1), (0, 0, 2), (1, 0, 2), (2, 0, 0)] {
+        board.set(handle, Tile::Blue);
+    }
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.pieces_cleared, 8);
+    assert!(board.is_empty());
+}
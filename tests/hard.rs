@@ -0,0 +1,59 @@
+mod common;
+
+use common::Grid;
+use owanimo::{
+    quicksim::QuickSimBoard,
+    standard::{NuisanceBoard, TrivialPiecesCleared},
+};
+
+#[test]
+fn hard_nuisance_softens() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        r
+        r
+        r
+        rh
+    "
+    .parse::<Grid>()?;
+    assert_eq!(board.hit_points(&(1, 0)), 1);
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    assert_eq!(result.pieces_cleared, 4);
+    assert_eq!(board, "_o".parse::<Grid>()?);
+    Ok(())
+}
+
+#[test]
+fn hard_nuisance_clears_over_two_links() -> Result<(), Box<dyn std::error::Error>> {
+    //the greens only join up once the reds are gone, and the hit from the reds carries over
+    let mut board = "
+        g
+        g
+        g
+        r
+        r
+        rg
+        rh
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.pieces_cleared, 9);
+    assert!(board.is_empty());
+    Ok(())
+}
+
+#[test]
+fn two_groups_hit_twice() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        r_g
+        r_g
+        r_g
+        rHg
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.pieces_cleared, 8);
+    assert_eq!(board, "_o".parse::<Grid>()?);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use owanimo::{
+    Board,
+    gravity::GravityBoard,
+    hex::{Hex, HexBoard, HexGravity},
+    quicksim::QuickSimBoard,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::Tile;
+
+type Hexes = HexBoard<Tile, 4, 6>;
+
+#[test]
+fn hex_offsets_round_trip() {
+    for x in 0..4 {
+        for y in 0..6 {
+            assert_eq!(Hex::from_offset((x, y)).to_offset(), Some((x, y)));
+        }
+    }
+}
+
+#[test]
+fn hex_neighbors() {
+    let board = Hexes::default();
+    assert_eq!(board.neighbors(&Hex::from_offset((1, 2))).count(), 6);
+    assert_eq!(board.neighbors(&Hex::from_offset((0, 0))).count(), 2);
+    //the neighbors of an odd column sit level with it or half a hexagon higher
+    let mut beside = board
+        .neighbors(&Hex::from_offset((1, 2)))
+        .filter_map(Hex::to_offset)
+        .filter(|&(x, _)| x != 1)
+        .collect::<Vec<_>>();
+    beside.sort();
+    assert_eq!(beside, vec![(0, 2), (0, 3), (2, 2), (2, 3)]);
+}
+
+#[test]
+fn hex_straight_and_sliding_gravity() -> Result<(), Box<dyn std::error::Error>> {
+    let drawn = "
+        r___
+        b___
+    ";
+    let mut straight = drawn.parse::<Hexes>()?;
+    assert!(!straight.fall());
+    assert_eq!(straight, drawn.parse::<Hexes>()?);
+    let mut sliding = drawn.parse::<Hexes>()?;
+    sliding.gravity = HexGravity::Slide;
+    assert!(sliding.fall());
+    //red can't go down or left, so it rolls down and to the right, twice
+    assert_eq!(sliding.get(Hex::from_offset((0, 0))), Tile::Blue);
+    assert_eq!(sliding.get(Hex::from_offset((1, 0))), Tile::Air);
+    assert_eq!(sliding.get(Hex::from_offset((2, 0))), Tile::Red);
+    assert!(!sliding.fall());
+    Ok(())
+}
+
+#[test]
+fn hex_quick_sim() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        b_
+        b_
+        r_
+        r_
+        rb
+        br
+    "
+    .parse::<Hexes>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.pieces_cleared, 8);
+    assert!(board.tiles().all(|h| board.get(h) == Tile::Air));
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use owanimo::{
+    BanishBoard, GroupSet, Scorer, gravity::GravityBoard, lines::LineBoard,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::Grid;
+
+#[test]
+fn runs_and_crossings() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        r_gg
+        r_gg
+        rrrbbb
+        bb
+    "
+    .parse::<Grid>()?;
+    let lines = board.owanimo_lines(3);
+    let mut sizes: Vec<usize> = lines.iter().map(|g| g.len()).collect();
+    sizes.sort();
+    //the red L is one group, the square of greens isn't a line and the pair of blues is too short
+    assert_eq!(sizes, [3, 5]);
+    assert!(lines.test(&(0, 3)));
+    assert!(!lines.test(&(2, 3)));
+    assert_eq!(board.owanimo_lines(2).len(), 4);
+    Ok(())
+}
+
+#[test]
+fn lines_go_through_the_spell() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        g
+        b
+        rrro
+    "
+    .parse::<Grid>()?;
+    let lines = board.owanimo_lines(3);
+    let popped = lines.owanimo_nuisance(&board);
+    assert_eq!(TrivialPiecesCleared.score(&board, &popped), 4);
+    for group in &popped {
+        for handle in group.handles() {
+            board.banish(handle);
+        }
+    }
+    assert!(board.fall());
+    assert_eq!(board, "g\nb".parse::<Grid>()?);
+    Ok(())
+}
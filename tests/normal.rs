@@ -1,11 +1,16 @@
 use std::str::FromStr;
 
 use owanimo::{
-    Board, RefGroups, Scorer,
+    BanishBoard, Board, RefGroups, Scorer,
     gravity::{AutoGravityBoard, GravityBoard},
-    standard::{ColorBoard, NuisanceBoard},
+    quicksim::QuickSimBoard,
+    standard::{ColorBoard, NuisanceBoard, TrivialPiecesCleared},
 };
 
+mod common;
+
+use common::sorted_groups;
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
 enum Tile {
     #[default]
@@ -70,6 +75,7 @@ impl TileBoard {
             }
         }
     }
+    #[allow(clippy::needless_range_loop)]
     fn getcol(&self, x: usize) -> [Tile; 12] {
         let mut vals = [Tile::Air; 12];
         for y in 0..12 {
@@ -221,6 +227,7 @@ impl AutoGravityBoard for TileBoard {
     fn is_air(&self, handle: Self::Handle) -> bool {
         self.get(handle) == Tile::Air
     }
+    #[allow(clippy::needless_range_loop, clippy::needless_borrow)]
     fn mutate_columns(&mut self, mut mutater: impl FnMut(&Self, &mut [Self::Handle])) {
         for x in 0..6 {
             let mut col = [(x, 0); 12];
@@ -238,6 +245,14 @@ impl AutoGravityBoard for TileBoard {
     }
 }
 
+impl BanishBoard for TileBoard {
+    fn banish(&mut self, handle: Self::Handle) {
+        self.set(handle, Tile::Air);
+    }
+}
+
+impl QuickSimBoard for TileBoard {}
+
 #[test]
 fn integration_itself_works() -> Result<(), Box<dyn std::error::Error>> {
     let board = "
@@ -324,3 +339,95 @@ fn do_a_chain_autogravity() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(board.items, [[Tile::Air; 6]; 12]);
     Ok(())
 }
+
+#[test]
+fn union_find_matches_naive() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+    orbg
+    rbgyr
+    rbgyob
+    rbgyyb
+    rrppgb
+    "
+    .parse::<TileBoard>()?;
+    let fast = board.owanimo_grouper();
+    let naive = board.owanimo_grouper_naive();
+    assert_eq!(fast.len(), naive.len());
+    assert_eq!(sorted_groups(&fast), sorted_groups(&naive));
+    Ok(())
+}
+
+#[test]
+fn group_ids_are_stable() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "rrbbg".parse::<TileBoard>()?;
+    let mut groups = board.owanimo_grouper();
+    let red = groups.group_of(&(0, 0)).unwrap();
+    let blue = groups.group_of(&(2, 0)).unwrap();
+    assert_eq!(groups.group_of(&(1, 0)), Some(red));
+    assert_ne!(red, blue);
+    assert_eq!(groups.remove(red).map(|g| g.len()), Some(2));
+    assert!(!groups.contains(&(0, 0)));
+    assert_eq!(groups.group_of(&(3, 0)), Some(blue));
+    assert_eq!(groups.get(blue).map(|g| g.len()), Some(2));
+    let refs = groups.as_ref();
+    assert!(refs.test(&(4, 0)));
+    assert!(!refs.test(&(1, 0)));
+    assert_eq!(refs.find(&(2, 0)).map(|g| g.len()), Some(2));
+    //ids carry over into the `RefGroups`, and into the groups popped from it
+    let green = groups.group_of(&(4, 0)).unwrap();
+    assert!(refs.get(green).unwrap().contains(&(4, 0)));
+    assert_eq!(refs.get(red), None);
+    assert!(refs.as_slice()[red.0].is_none());
+    let popped = refs.owanimo_pop(2);
+    assert_eq!(popped.group_of(&(2, 0)), Some(blue));
+    assert_eq!(popped.get(green), None);
+    assert_eq!(popped.to_owned().group_of(&(3, 0)), Some(blue));
+    Ok(())
+}
+
+#[test]
+fn regroup_matches_full_grouping() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+    orbg
+    rbgy
+    rbgyo
+    rbgyy
+    "
+    .parse::<TileBoard>()?;
+    let mut groups = board.owanimo_grouper();
+    let mut changed = std::collections::HashSet::new();
+    let binding = groups.as_ref();
+    let binding = binding.owanimo_pop(4);
+    let popped = binding.owanimo_nuisance(&board).to_owned();
+    for g in popped.iter() {
+        for &h in g {
+            board.banish(h);
+            changed.insert(h);
+        }
+    }
+    assert!(board.fall_tracked(&mut changed));
+    board.owanimo_regroup(&mut groups, changed);
+    assert_eq!(
+        sorted_groups(&groups),
+        sorted_groups(&board.owanimo_grouper())
+    );
+    Ok(())
+}
+
+#[test]
+fn quick_sim_a_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+    orbg
+    rbgy
+    rbgyo
+    rbgyy
+    "
+    .parse::<TileBoard>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 4);
+    assert_eq!(result.pieces_cleared, 18);
+    assert_eq!(result.max_pieces_at_once, 5);
+    assert_eq!(result.score, 180);
+    assert_eq!(board.items, [[Tile::Air; 6]; 12]);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use owanimo::{
+    pieces::{Piece, PieceGenerator, PieceShape},
+    rng::SplitMix64,
+};
+
+const PALETTE: &[char] = &['r', 'g', 'b', 'y', 'p'];
+
+fn colors(piece: &Piece<char>) -> Vec<char> {
+    match *piece {
+        Piece::Pair { pivot, satellite } => vec![pivot, satellite],
+        Piece::Triple { pair, odd } => vec![pair, pair, odd],
+        Piece::Quad { a, b } => vec![a, a, b, b],
+        Piece::Big(c) => vec![c; 4],
+    }
+}
+
+#[test]
+fn same_seed_same_pieces() {
+    let a: Vec<_> = PieceGenerator::new(PALETTE, 4, SplitMix64::new(99))
+        .take(64)
+        .collect();
+    let b: Vec<_> = PieceGenerator::new(PALETTE, 4, SplitMix64::new(99))
+        .take(64)
+        .collect();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn colors_are_restricted() {
+    for seed in 0..32 {
+        let mut pieces = PieceGenerator::new(PALETTE, 4, SplitMix64::new(seed));
+        assert_eq!(pieces.colors.len(), 4);
+        let first: Vec<char> = pieces.by_ref().take(2).flat_map(|p| colors(&p)).collect();
+        assert!(first.iter().all(|c| pieces.colors[..3].contains(c)));
+        let rest: Vec<char> = pieces.take(256).flat_map(|p| colors(&p)).collect();
+        //only 4 of the 5 colors ever show up, and all 4 of them do
+        let used = PALETTE.iter().filter(|c| rest.contains(c)).count();
+        assert_eq!(used, 4);
+    }
+}
+
+#[test]
+fn next_and_next_next() {
+    let mut pieces = PieceGenerator::new(PALETTE, 4, SplitMix64::new(5));
+    pieces.shapes = vec![
+        PieceShape::Pair,
+        PieceShape::Triple,
+        PieceShape::Quad,
+        PieceShape::Big,
+    ];
+    let next = *pieces.peek(0).unwrap();
+    let next_next = *pieces.peek(1).unwrap();
+    assert_eq!(pieces.dealt(), 2);
+    assert_eq!(pieces.next(), Some(next));
+    assert_eq!(pieces.next(), Some(next_next));
+    assert_eq!(next.shape(), PieceShape::Pair);
+    assert_eq!(next_next.shape(), PieceShape::Triple);
+    assert_eq!(pieces.next().map(|p| p.shape()), Some(PieceShape::Quad));
+    assert_eq!(pieces.next().map(|p| p.shape()), Some(PieceShape::Big));
+    assert_eq!(pieces.next().map(|p| p.shape()), Some(PieceShape::Pair));
+    assert_eq!(
+        PieceGenerator::new(&[] as &[char], 4, SplitMix64::new(5)).next(),
+        None
+    );
+}
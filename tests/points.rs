@@ -0,0 +1,124 @@
+use owanimo::{
+    Board, Scorer,
+    garbage::GarbageCalculator,
+    quicksim::QuickSimBoard,
+    standard::{
+        ColorBonusTable, ColoredPiecesCleared, GroupBonusTable, PointBonus, SunBonus,
+        TrivialPiecesCleared,
+    },
+};
+
+mod common;
+
+use common::Grid;
+
+///Puyo Puyo Tsu's tables.
+const CHAIN_POWER: &[u64] = &[0, 8, 16, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320];
+const COLOR_BONUS: ColorBonusTable = ColorBonusTable {
+    table: &[0, 0, 3, 6, 12, 24],
+};
+const GROUP_BONUS: GroupBonusTable = GroupBonusTable {
+    table: &[0, 0, 0, 0, 0, 2, 3, 4, 5, 6, 7, 10],
+};
+
+#[test]
+fn tsu_values() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        _b
+        rg
+        rgb
+        rgb
+        rgbo
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(
+        &4,
+        &ColoredPiecesCleared,
+        &(),
+        CHAIN_POWER,
+        &COLOR_BONUS,
+        &GROUP_BONUS,
+    );
+    //1st link: 8 reds and greens, 10 * 8 = 80, times 0 chain power + 3 for two colours
+    //2nd link: 4 blues, 10 * 4 = 40, times 8 chain power, and the nuisance is worth nothing
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.pieces_cleared, 12);
+    assert_eq!(result.score, 80 * 3 + 40 * 8);
+    Ok(())
+}
+
+#[test]
+fn point_pieces_in_a_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        bb
+        rb
+        rrb
+        rrpp
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(
+        &4,
+        &ColoredPiecesCleared,
+        &PointBonus,
+        CHAIN_POWER,
+        &COLOR_BONUS,
+        &GROUP_BONUS,
+    );
+    //1st link: 5 reds and the Point piece next to them, 10 * 5 + 50 = 100,
+    // times 0 chain power + 0 for one colour + 2 for a group of 5
+    //2nd link: the blues fall into a group of 4 next to the other Point piece, 10 * 4 + 50 = 90,
+    // times 8 chain power
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.pieces_cleared, 9);
+    assert_eq!(result.score, 100 * 2 + 90 * 8);
+    assert!(board.is_empty());
+    Ok(())
+}
+
+#[test]
+fn sun_pieces() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        *__*
+        rrrr*
+    "
+    .parse::<Grid>()?;
+    let groups = board.owanimo_grouper();
+    let groups = groups.as_ref();
+    let popped = groups.owanimo_pop(4);
+    let popped = popped.owanimo_nuisance(&board);
+    //all three Suns touch the reds
+    assert_eq!(SunBonus { per_sun: 3 }.score(&board, &popped), 3 * 3);
+    assert_eq!(TrivialPiecesCleared.score(&board, &popped), 4 + 3);
+    assert_eq!(ColoredPiecesCleared.score(&board, &popped), 4);
+    Ok(())
+}
+
+#[test]
+fn sun_garbage_from_a_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        g
+        *__*
+        rrrr
+        gggo
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim_with_garbage(
+        &4,
+        &ColoredPiecesCleared,
+        &(),
+        &[8, 16],
+        &(),
+        &(),
+        &SunBonus { per_sun: 3 },
+    );
+    //1st link: 4 reds, 10 * 4 * 8, which take both Suns and the nuisance with them
+    //2nd link: the top green falls into the other 3, 10 * 4 * 16
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.link_scores, [40 * 8, 40 * 16]);
+    assert_eq!(result.pieces_cleared, 8);
+    assert_eq!(result.bonus_garbage, 2 * 3);
+    //320 / 70 is 4 with 40 left over, and (640 + 40) / 70 is 9, then the Suns send 6 more
+    assert_eq!(GarbageCalculator::default().add_sim(&result), 4 + 9 + 6);
+    assert!(board.is_empty());
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use owanimo::{
+    Board, PopRule,
+    pop::{And, ColorThresholds, Not, Or, TouchesRow},
+    quicksim::QuickSimBoard,
+    standard::TrivialPiecesCleared,
+};
+
+mod common;
+
+use common::{Grid, Tile};
+
+///How many beings each rule pops in one cast.
+fn popped(board: &Grid, rule: &impl PopRule<Grid>) -> usize {
+    let groups = board.owanimo_grouper();
+    let groups = groups.as_ref();
+    groups
+        .owanimo_pop_by(board, rule)
+        .iter()
+        .map(|g| g.len())
+        .sum()
+}
+
+#[test]
+fn size_and_combinators() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        g
+        g
+        gr
+        grrr
+    "
+    .parse::<Grid>()?;
+    assert_eq!(popped(&board, &4), 8);
+    assert_eq!(popped(&board, &5), 0);
+    assert_eq!(popped(&board, &And(4, TouchesRow(2))), 4);
+    assert_eq!(popped(&board, &And(Or(TouchesRow(3), TouchesRow(2)), 4)), 4);
+    assert_eq!(popped(&board, &And(Not(TouchesRow(3)), 4)), 4);
+    Ok(())
+}
+
+#[test]
+fn per_colour_thresholds() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        g
+        gr
+        grr
+        o
+    "
+    .parse::<Grid>()?;
+    let rule = ColorThresholds {
+        table: &[(Tile::Red, 3)],
+        otherwise: 4,
+    };
+    assert_eq!(popped(&board, &rule), 3);
+    //nuisance has no colour, so it never pops even with a threshold of 1
+    let rule = ColorThresholds {
+        table: &[],
+        otherwise: 1,
+    };
+    assert_eq!(popped(&board, &rule), 6);
+    Ok(())
+}
+
+#[test]
+fn quick_sim_with_a_rule() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        g
+        rrr
+        ggggo
+    "
+    .parse::<Grid>()?;
+    let rule = ColorThresholds {
+        table: &[(Tile::Green, 5)],
+        otherwise: 3,
+    };
+    let result = board.quick_sim(&rule, &TrivialPiecesCleared, &(), &[1], &(), &());
+    //the reds pop, then the greens fall together into a group of 5 and pop with the nuisance
+    assert_eq!(result.chain, 2);
+    assert_eq!(result.pieces_cleared, 9);
+    assert!(board.is_empty());
+    Ok(())
+}
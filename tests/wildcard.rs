@@ -0,0 +1,115 @@
+use owanimo::{
+    Board,
+    quicksim::QuickSimBoard,
+    standard::{ColorBonusTable, GroupBonusTable, TrivialPiecesCleared},
+};
+
+mod common;
+
+use common::{Grid, Tile, sorted_groups};
+
+#[test]
+fn wildcards_join_without_merging() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        r_b
+        rwb
+    "
+    .parse::<Grid>()?;
+    let mut groups = board.owanimo_grouper();
+    assert!(groups.overlapping());
+    let red = groups.get(groups.group_of(&(0, 0)).unwrap()).unwrap();
+    assert_eq!(red.len(), 3);
+    assert!(red.contains(&(1, 0)) && !red.contains(&(2, 0)));
+    let blue = groups.group_of(&(2, 0)).unwrap();
+    assert_eq!(groups.get(blue).unwrap().len(), 3);
+    //taking one group out leaves the wildcard findable in the other
+    groups.find(&(0, 0));
+    groups.find(&(2, 0));
+    assert_eq!(groups.group_of(&(1, 0)), None);
+    Ok(())
+}
+
+#[test]
+fn wildcards_bridge_the_same_colour() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        r_ww
+        rwwr
+    "
+    .parse::<Grid>()?;
+    let groups = board.owanimo_grouper();
+    //the reds join up through the wildcards, and the wildcards go along with them
+    let red = groups.get(groups.group_of(&(0, 0)).unwrap()).unwrap();
+    assert_eq!(red.len(), 7);
+    assert!(!groups.overlapping());
+    let lonely = "ww".parse::<Grid>()?.owanimo_grouper();
+    assert_eq!(
+        lonely.get(lonely.group_of(&(0, 0)).unwrap()).unwrap().len(),
+        2
+    );
+    Ok(())
+}
+
+#[test]
+fn wildcards_score_once() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        r_b
+        r_b
+        rwb
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(
+        &4,
+        &TrivialPiecesCleared,
+        &(),
+        &[1],
+        &ColorBonusTable { table: &[0, 0, 3] },
+        &GroupBonusTable {
+            table: &[0, 0, 0, 0, 2],
+        },
+    );
+    assert_eq!(result.chain, 1);
+    assert_eq!(result.pieces_cleared, 7);
+    //both groups get a group bonus, and the rainbow doesn't count as a third colour
+    assert_eq!(result.score, 70 * (1 + 3 + 2 + 2));
+    assert!(board.is_empty());
+    Ok(())
+}
+
+#[test]
+fn wildcards_leave_with_one_group() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "
+        r
+        r_b
+        rwb
+    "
+    .parse::<Grid>()?;
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    assert_eq!(result.pieces_cleared, 4);
+    assert_eq!(board, "__b\n__b".parse::<Grid>()?);
+    Ok(())
+}
+
+#[test]
+fn regroup_only_starts_over_near_wildcards() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = "rw___b".parse::<Grid>()?;
+    let mut groups = board.owanimo_grouper();
+    let red = groups.group_of(&(0, 0)).unwrap();
+    //far from the rainbow, so the red group keeps its id
+    board.set((5, 0), Tile::Air);
+    board.owanimo_regroup(&mut groups, [(5, 0)]);
+    assert_eq!(groups.group_of(&(1, 0)), Some(red));
+    assert_eq!(
+        sorted_groups(&groups),
+        sorted_groups(&board.owanimo_grouper())
+    );
+    //a rainbow turning up between two groups is grouped from scratch
+    board.set((2, 0), Tile::Rainbow);
+    board.set((3, 0), Tile::Blue);
+    board.owanimo_regroup(&mut groups, [(2, 0), (3, 0)]);
+    assert_eq!(
+        sorted_groups(&groups),
+        sorted_groups(&board.owanimo_grouper())
+    );
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use owanimo::{Board, quicksim::QuickSimBoard, standard::TrivialPiecesCleared, wrap::Wrapping};
+
+mod common;
+
+use common::{Grid, Tile, group_size};
+
+#[test]
+fn groups_join_across_the_seam() -> Result<(), Box<dyn std::error::Error>> {
+    let board = "
+        r____r
+        r____r
+    "
+    .parse::<Grid>()?;
+    assert_eq!(group_size(&board, (0, 0)), 2);
+    let wrapped = Wrapping::horizontal(board);
+    assert_eq!(group_size(&wrapped, (0, 0)), 4);
+    assert_eq!(wrapped.neighbors(&(5, 0)).count(), 3);
+    Ok(())
+}
+
+#[test]
+fn torus_joins_top_and_bottom() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = Grid::new();
+    for at in [(2, 0), (3, 0), (2, 11), (3, 11)] {
+        board.set(at, Tile::Green);
+    }
+    assert_eq!(group_size(&Wrapping::horizontal(board), (2, 0)), 2);
+    assert_eq!(group_size(&Wrapping::torus(board), (2, 0)), 4);
+    Ok(())
+}
+
+#[test]
+fn wrapped_quick_sim() -> Result<(), Box<dyn std::error::Error>> {
+    //the nuisance is only next to the reds through the seam
+    let mut board = Wrapping::horizontal(
+        "
+        g
+        r
+        r
+        rg
+        r___go
+        "
+        .parse::<Grid>()?,
+    );
+    let result = board.quick_sim(&4, &TrivialPiecesCleared, &(), &[1], &(), &());
+    assert_eq!(result.chain, 1);
+    assert_eq!(result.pieces_cleared, 5);
+    assert_eq!(
+        board.inner,
+        "
+        gg__g
+        "
+        .parse::<Grid>()?
+    );
+    Ok(())
+}